@@ -0,0 +1,80 @@
+// src/configuration/alarms.rs
+// github.com/cvusmo/hyprclock
+
+use chrono::{NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AlarmConfig {
+    pub time: String, // HH:MM, local time
+    pub label: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub weekdays: Vec<String>, // mon..sun, empty means every day
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_snooze_minutes")]
+    pub snooze_minutes: u32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_snooze_minutes() -> u32 {
+    9
+}
+
+impl AlarmConfig {
+    pub fn new(time: &str, label: &str, weekdays: Vec<String>) -> Self {
+        AlarmConfig {
+            time: time.to_string(),
+            label: label.to_string(),
+            weekdays,
+            enabled: default_enabled(),
+            snooze_minutes: default_snooze_minutes(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.time_of_day().is_none() {
+            return Err(format!(
+                "Invalid alarm time '{}' for '{}', expected HH:MM",
+                self.time, self.label
+            ));
+        }
+
+        if let Some(day) = self
+            .weekdays
+            .iter()
+            .find(|day| parse_weekday(day).is_none())
+        {
+            return Err(format!("Invalid weekday '{}' for '{}'", day, self.label));
+        }
+
+        if self.snooze_minutes == 0 {
+            return Err(format!(
+                "Snooze for '{}' must be at least one minute",
+                self.label
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn time_of_day(&self) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(self.time.trim(), "%H:%M").ok()
+    }
+
+    pub fn rings_on(&self, weekday: Weekday) -> bool {
+        self.weekdays.is_empty()
+            || self
+                .weekdays
+                .iter()
+                .any(|day| parse_weekday(day) == Some(weekday))
+    }
+}
+
+// Function to parse weekday names such as "mon" or "Monday"
+pub fn parse_weekday(day: &str) -> Option<Weekday> {
+    day.trim().parse::<Weekday>().ok()
+}
@@ -0,0 +1,38 @@
+// src/configuration/analog.rs
+// github.com/cvusmo/hyprclock
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AnalogConfig {
+    pub second_hand: bool,
+    pub tick_marks: bool,
+    pub numerals: String, // none, arabic or roman
+}
+
+impl AnalogConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let valid_numerals = ["none", "arabic", "roman"];
+        if valid_numerals.contains(&self.numerals.as_str()) {
+            Ok(())
+        } else {
+            Err(format!("Invalid numeral style: {}", self.numerals))
+        }
+    }
+}
+
+// Default AnalogConfig
+impl Default for AnalogConfig {
+    fn default() -> Self {
+        AnalogConfig {
+            second_hand: true,
+            tick_marks: true,
+            numerals: "arabic".to_string(),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+// src/configuration/calendar.rs
+// github.com/cvusmo/hyprclock
+
+use crate::time::locale::first_weekday;
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct CalendarConfig {
+    pub week_numbers: bool, // ISO 8601 week column in the tooltip and popover
+    pub week_start: String, // "locale" or a weekday such as "monday" or "sun"
+    pub months: u32, // months in the waybar tooltip, centred on the shown one; 12 for the year
+    pub months_per_row: u32,
+    pub upcoming: usize, // events listed under "Upcoming" in the tooltip, 0 to hide
+    pub upcoming_days: u32, // how far ahead to look for them
+}
+
+impl CalendarConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.week_start != "locale" && self.week_start.parse::<Weekday>().is_err() {
+            return Err(format!(
+                "Invalid week start '{}', expected 'locale' or a weekday",
+                self.week_start
+            ));
+        }
+        if !(1..=12).contains(&self.months) {
+            return Err(format!(
+                "Calendar months must be between 1 and 12: {}",
+                self.months
+            ));
+        }
+        if !(1..=12).contains(&self.months_per_row) {
+            return Err(format!(
+                "Calendar months_per_row must be between 1 and 12: {}",
+                self.months_per_row
+            ));
+        }
+        if !(1..=366).contains(&self.upcoming_days) {
+            return Err(format!(
+                "Calendar upcoming_days must be between 1 and 366: {}",
+                self.upcoming_days
+            ));
+        }
+        Ok(())
+    }
+
+    // Function to resolve the first column of the tooltip calendar
+    pub fn first_weekday(&self) -> Weekday {
+        self.week_start
+            .parse::<Weekday>()
+            .unwrap_or_else(|_| first_weekday())
+    }
+}
+
+// Default CalendarConfig
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        CalendarConfig {
+            week_numbers: false,
+            week_start: String::from("locale"),
+            months: 1,
+            months_per_row: 3,
+            upcoming: 5,
+            upcoming_days: 14,
+        }
+    }
+}
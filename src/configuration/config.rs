@@ -1,33 +1,86 @@
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
-use std::{fs, io, path::PathBuf};
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
 use toml;
 
+pub use crate::configuration::alarms::AlarmConfig;
+pub use crate::configuration::analog::AnalogConfig;
 pub use crate::configuration::animation::AnimationConfig;
+pub use crate::configuration::calendar::CalendarConfig;
+pub use crate::configuration::date::DateConfig;
 pub use crate::configuration::env::EnvConfig;
 pub use crate::configuration::general::GeneralConfig;
+pub use crate::configuration::holidays::HolidaysConfig;
 use crate::configuration::logger::AppState;
+pub use crate::configuration::pomodoro::PomodoroConfig;
+pub use crate::configuration::stopwatch::StopwatchConfig;
 pub use crate::configuration::theme::ThemeConfig;
+pub use crate::configuration::timers::TimersConfig;
 use crate::configuration::validate::{
-    validate_animations, validate_environment, validate_general, validate_theme,
+    validate_alarms, validate_analog, validate_animations, validate_calendar, validate_date,
+    validate_environment, validate_general, validate_holidays, validate_pomodoro, validate_theme,
+    validate_timers, validate_waybar, validate_zones,
 };
+pub use crate::configuration::waybar::WaybarConfig;
+pub use crate::configuration::zones::ZoneConfig;
+
+const CONFIG_FILE_NAME: &str = "hyprclock.conf";
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alarms: Vec<AlarmConfig>,
+    #[serde(default)]
+    pub analog: AnalogConfig,
     pub animation: AnimationConfig,
+    #[serde(default)]
+    pub calendar: CalendarConfig,
+    #[serde(default)]
+    pub date: DateConfig,
     pub env: EnvConfig,
     pub general: GeneralConfig,
+    #[serde(default)]
+    pub holidays: HolidaysConfig,
+    #[serde(default)]
+    pub pomodoro: PomodoroConfig,
+    #[serde(default)]
+    pub stopwatch: StopwatchConfig,
     pub theme: ThemeConfig,
+    #[serde(default)]
+    pub timers: TimersConfig,
+    #[serde(default)]
+    pub waybar: WaybarConfig,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub zones: Vec<ZoneConfig>,
+    #[serde(skip)]
+    pub path: Option<PathBuf>, // file this config was loaded from, and where save writes
+    #[serde(skip)]
+    pub warnings: Vec<String>, // deprecated keys found while loading, for the caller to log
 }
 
 impl Config {
     /// Create a new configuration with default values
     pub fn new() -> Self {
         Config {
+            alarms: Vec::new(),
+            analog: AnalogConfig::new(),
             animation: AnimationConfig::new(),
+            calendar: CalendarConfig::new(),
+            date: DateConfig::new(),
             env: EnvConfig::new(),
             general: GeneralConfig::new(),
+            holidays: HolidaysConfig::new(),
+            pomodoro: PomodoroConfig::new(),
+            stopwatch: StopwatchConfig::new(),
             theme: ThemeConfig::new(),
+            timers: TimersConfig::new(),
+            waybar: WaybarConfig::new(),
+            zones: Vec::new(),
+            path: None,
+            warnings: Vec::new(),
         }
     }
 
@@ -39,11 +92,14 @@ impl Config {
 
         if config_path.exists() {
             let config_contents = fs::read_to_string(&config_path)?;
-            let loaded_config: Self = toml::from_str(&config_contents)
+            let mut loaded_config: Self = toml::from_str(&config_contents)
                 .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            loaded_config.path = Some(config_path);
+            loaded_config.migrate_deprecated();
 
             // Validate the loaded configuration using validate.rs
             Config::validate_config(&loaded_config)?;
+            loaded_config.load_holidays()?;
             Ok(loaded_config)
         } else {
             // Create default config using load functions of each submodule
@@ -53,20 +109,52 @@ impl Config {
             // otherwise this else statement is to create a NEW animation, env
             // general, theme, to then save it as the default config because
             // *Config::new() IS the default setting
-            let default_config = Config {
+            let mut default_config = Config {
+                alarms: Vec::new(),
+                analog: AnalogConfig::new(),
                 animation: AnimationConfig::new(),
+                calendar: CalendarConfig::new(),
+                date: DateConfig::new(),
                 env: EnvConfig::new(),
                 general: GeneralConfig::new(),
+                holidays: HolidaysConfig::new(),
+                pomodoro: PomodoroConfig::new(),
+                stopwatch: StopwatchConfig::new(),
                 theme: ThemeConfig::new(),
+                timers: TimersConfig::new(),
+                waybar: WaybarConfig::new(),
+                zones: Vec::new(),
+                path: Some(config_path),
+                warnings: Vec::new(),
             };
             default_config.save()?;
+            default_config.load_holidays()?;
             Ok(default_config)
         }
     }
 
-    /// Save the configuration to file
+    /// Build the holiday calendar once per load, so renders do not read the rule file again
+    fn load_holidays(&mut self) -> io::Result<()> {
+        self.holidays
+            .load_calendar()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Move keys from older versions to where they live now; the next save drops the old ones
+    fn migrate_deprecated(&mut self) {
+        if let Some(date_format) = self.general.date_format.take() {
+            self.warnings
+                .push("general.date_format is deprecated, use format in [date]".to_string());
+            // The old key turned the date line on by being set
+            self.date.format = date_format;
+            self.date.enabled = true;
+        }
+    }
+
+    /// Save the configuration to the file it was loaded from, or to the user's file when it
+    /// came from a system directory
     pub fn save(&self) -> io::Result<()> {
-        let config_path = get_config_path();
+        let config_path = get_save_path(&self.path.clone().unwrap_or_else(get_config_path));
 
         if let Some(parent) = config_path.parent() {
             fs::create_dir_all(parent)?;
@@ -84,15 +172,42 @@ impl Config {
         if let Err(err) = validate_animations(&[config.animation.clone()]) {
             errors.push(err);
         }
+        if let Err(err) = validate_alarms(&config.alarms) {
+            errors.push(err);
+        }
+        if let Err(err) = validate_analog(&config.analog) {
+            errors.push(err);
+        }
+        if let Err(err) = validate_calendar(&config.calendar) {
+            errors.push(err);
+        }
+        if let Err(err) = validate_date(&config.date, &config.general.placeholders()) {
+            errors.push(err);
+        }
         if let Err(err) = validate_general(&config.general) {
             errors.push(err);
         }
+        if let Err(err) = validate_holidays(&config.holidays) {
+            errors.push(err);
+        }
+        if let Err(err) = validate_pomodoro(&config.pomodoro) {
+            errors.push(err);
+        }
         if let Err(err) = validate_theme(&config.theme) {
             errors.push(err);
         }
+        if let Err(err) = validate_timers(&config.timers) {
+            errors.push(err);
+        }
+        if let Err(err) = validate_waybar(&config.waybar) {
+            errors.push(err);
+        }
         if let Err(err) = validate_environment(&config.env) {
             errors.push(err);
         }
+        if let Err(err) = validate_zones(&config.zones, &config.general.placeholders()) {
+            errors.push(err);
+        }
 
         if errors.is_empty() {
             Ok(())
@@ -110,9 +225,82 @@ impl Config {
     }
 }
 
-/// Get the path to the configuration file
+/// Get the user's config directory: $XDG_CONFIG_HOME/hypr, like the other Hypr tools
+pub fn get_config_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| {
+            let mut path = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+            path.push(".config");
+            path
+        })
+        .join("hypr")
+}
+
+/// Config files in the order they are tried: the user's file, then a system-wide one from
+/// each of $XDG_CONFIG_DIRS (default /etc/xdg)
+pub fn config_search_paths() -> Vec<PathBuf> {
+    let system_dirs = env::var("XDG_CONFIG_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/etc/xdg".to_string());
+
+    let mut paths = vec![get_config_dir().join(CONFIG_FILE_NAME)];
+    paths.extend(
+        system_dirs
+            .split(':')
+            .map(PathBuf::from)
+            // Relative entries are invalid per the spec and are ignored
+            .filter(|dir| dir.is_absolute())
+            .map(|dir| dir.join("hypr").join(CONFIG_FILE_NAME)),
+    );
+    paths
+}
+
+/// Get the path to the configuration file: the first one found, or the user's file when
+/// there is none yet
 pub fn get_config_path() -> PathBuf {
-    let mut path = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
-    path.push(".config/hypr/hyprclock.conf");
-    path
+    let paths = config_search_paths();
+    paths
+        .iter()
+        .find(|path| path.exists())
+        .unwrap_or(&paths[0])
+        .clone()
+}
+
+/// Get the file saves go to for a config loaded from `path`: the same file, except that a
+/// system-wide one is left alone and the user's own copy is written instead
+pub fn get_save_path(path: &Path) -> PathBuf {
+    if config_search_paths()
+        .iter()
+        .skip(1)
+        .any(|system| system == path)
+    {
+        get_config_dir().join(CONFIG_FILE_NAME)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Get the directory for runtime state such as running timers and the log:
+/// $XDG_STATE_HOME/hyprclock
+pub fn get_state_dir() -> PathBuf {
+    dirs::state_dir()
+        .unwrap_or_else(|| {
+            let mut path = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+            path.push(".local/state");
+            path
+        })
+        .join("hyprclock")
+}
+
+/// Get the directory for files that are safe to delete, such as debug logs:
+/// $XDG_CACHE_HOME/hyprclock
+pub fn get_cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| {
+            let mut path = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+            path.push(".cache");
+            path
+        })
+        .join("hyprclock")
 }
@@ -0,0 +1,58 @@
+// src/configuration/date.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::format::validate_format;
+use crate::time::placeholders::{validate_placeholders, Placeholders};
+use glib::DateTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DateConfig {
+    pub enabled: bool,
+    pub format: String, // strftime pattern
+    pub font_size: f32,
+}
+
+impl DateConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn validate(&self, placeholders: &Placeholders) -> Result<(), String> {
+        validate_format(&self.format)
+            .and_then(|_| validate_placeholders(&self.format, placeholders))
+            .map_err(|e| format!("Invalid date format: {}", e))?;
+
+        // Same range as the theme font size
+        if self.font_size <= 0.0 || self.font_size > 72.0 {
+            return Err(
+                "Date font size must be greater than 0 and less than or equal to 72.".to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    // Placeholders expand like in the time format, e.g. "%A, %d %B  week {week}"
+    pub fn format_date(&self, time: &DateTime, placeholders: &Placeholders) -> String {
+        time.format(&placeholders.expand_for_strftime(&self.format, time))
+            .expect("Format failed")
+            .to_string()
+    }
+
+    pub fn get_current_date(&self, placeholders: &Placeholders) -> String {
+        let now = DateTime::now_local().expect("Failed to get local time");
+        self.format_date(&now, placeholders)
+    }
+}
+
+// Default DateConfig
+impl Default for DateConfig {
+    fn default() -> Self {
+        DateConfig {
+            enabled: false,
+            format: String::from("%A, %d %B %Y"),
+            font_size: 24.0,
+        }
+    }
+}
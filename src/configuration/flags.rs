@@ -1,11 +1,13 @@
 // In src/main.rs, modify Args struct in configuration/flags.rs
-use clap::{ArgAction, Parser};
+use crate::tools::bar::BAR_BACKENDS;
+use clap::{ArgAction, ArgGroup, Parser};
 
 #[derive(Parser, Debug)]
 #[command(
     version = "0.1.2",
     about = "Hyprclock - a clock widget for Time Wizards"
 )]
+#[command(group(ArgGroup::new("bar_output").args(["waybar", "bar"])))]
 pub struct Args {
     #[arg(long, action = ArgAction::SetTrue)]
     pub debug: bool,
@@ -13,6 +15,28 @@ pub struct Args {
     pub log: bool,
     #[arg(short, long, value_name = "FILE")]
     pub config: Option<String>,
+    /// Same as --bar waybar
     #[arg(long, action = ArgAction::SetTrue)]
     pub waybar: bool,
+    /// Print the clock for a status bar; i3bar and yambar always stream
+    #[arg(long, value_name = "BACKEND", value_parser = BAR_BACKENDS)]
+    pub bar: Option<String>,
+    /// With --waybar or --bar, keep running and print an update whenever the output changes
+    #[arg(long, action = ArgAction::SetTrue, requires = "bar_output")]
+    pub stream: bool,
+    /// Page the waybar tooltip calendar, e.g. from on-scroll-up/on-scroll-down
+    #[arg(long, value_name = "ACTION", value_parser = ["next-month", "prev-month", "reset"])]
+    pub waybar_action: Option<String>,
+    /// Start a countdown, e.g. --timer standup 15m
+    #[arg(long, num_args = 2, value_names = ["NAME", "DURATION"])]
+    pub timer: Option<Vec<String>>,
+    /// Cancel a running countdown by name
+    #[arg(long, value_name = "NAME")]
+    pub timer_cancel: Option<String>,
+    /// Control the pomodoro cycle
+    #[arg(long, value_name = "ACTION", value_parser = ["start", "stop", "toggle", "skip"])]
+    pub pomodoro: Option<String>,
+    /// Print focus minutes per day from the pomodoro history
+    #[arg(long, action = ArgAction::SetTrue)]
+    pub pomodoro_history: bool,
 }
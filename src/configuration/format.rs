@@ -0,0 +1,101 @@
+// src/configuration/format.rs
+// github.com/cvusmo/hyprclock
+
+// Conversion characters understood by glib::DateTime::format
+const CONVERSIONS: &str = "aAbBcCdefFgGhHIjklmMnpPrRStTuVwxXyYzZ%";
+
+// Flags allowed between '%' and the conversion character
+const MODIFIERS: &str = "-_0EO";
+
+// Function to validate a strftime-style format string
+pub fn validate_format(format: &str) -> Result<(), String> {
+    if format.trim().is_empty() {
+        return Err("Format string must not be empty".to_string());
+    }
+
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+
+        let mut specifier = String::from("%");
+        while let Some(&modifier) = chars.peek() {
+            if !MODIFIERS.contains(modifier) {
+                break;
+            }
+            specifier.push(modifier);
+            chars.next();
+        }
+
+        // %:z, %::z and %:::z are the only specifiers that take colons
+        let mut colons = 0;
+        while chars.peek() == Some(&':') {
+            colons += 1;
+            specifier.push(':');
+            chars.next();
+        }
+
+        match chars.next() {
+            Some(conversion) => {
+                specifier.push(conversion);
+                let valid_colons = colons == 0 || (conversion == 'z' && colons <= 3);
+                if !valid_colons || !CONVERSIONS.contains(conversion) {
+                    return Err(format!(
+                        "Unknown format specifier '{}' in \"{}\"",
+                        specifier, format
+                    ));
+                }
+            }
+            None => {
+                return Err(format!(
+                    "Incomplete format specifier '{}' at end of \"{}\"",
+                    specifier, format
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Function to list the conversion characters used by a format string
+pub fn conversions(format: &str) -> Vec<char> {
+    let mut result = Vec::new();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        if let Some(conversion) = chars.find(|c| !MODIFIERS.contains(*c) && *c != ':') {
+            result.push(conversion);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_glib_specifiers() {
+        assert!(validate_format("%H:%M:%S.%f").is_ok());
+        assert!(validate_format("%-d %B %Y, %:z").is_ok());
+        assert!(validate_format("100%%").is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_specifiers() {
+        assert!(validate_format("%Q").is_err());
+        assert!(validate_format("%:H").is_err());
+        assert!(validate_format("%H:%").is_err());
+        assert!(validate_format("  ").is_err());
+    }
+
+    #[test]
+    fn lists_conversions() {
+        assert_eq!(conversions("%H:%M:%S.%f"), vec!['H', 'M', 'S', 'f']);
+        assert_eq!(conversions("%-d %::z"), vec!['d', 'z']);
+    }
+}
@@ -1,27 +1,57 @@
 // src/configuration/general.rs
+use crate::configuration::format::validate_format;
 use crate::configuration::logger::{log_info, AppState};
-use glib::{ControlFlow::Continue, DateTime};
+use crate::time::locale::{locale_available, uses_12_hour};
+use crate::time::modes::{formatter_for, TimeFormatter, DISPLAY_MODES};
+use crate::time::placeholders::{validate_placeholders, Placeholders};
+use crate::time::sun::validate_coordinates;
+use crate::time::tick::Ticker;
+use glib::DateTime;
 use gtk4::Label;
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 
+// Date line used in the tooltip header when the [date] line is off
+pub const DEFAULT_DATE_FORMAT: &str = "%Y %B";
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct GeneralConfig {
-    pub clock_format: String,   // 12-hour or 24-hour
+    pub clock_format: String,   // 12-hour, 24-hour or locale
     pub time_precision: String, // short or long
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>, // strftime pattern, overrides the presets above
+    #[serde(skip_serializing)]
+    pub date_format: Option<String>, // deprecated, moved to [date] format on load
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>, // overrides LC_TIME, e.g. "de_DE.UTF-8"
+    pub display: String,        // digits, words, binary, bcd, epoch, beat or hex
+    pub mode: String,           // digital, analog or both
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latitude: Option<f64>, // for sunrise/sunset, degrees north
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub longitude: Option<f64>, // degrees east
 }
 
 impl GeneralConfig {
     pub fn new() -> Self {
         GeneralConfig {
-            clock_format: String::from("12-hour"),
+            clock_format: String::from("locale"),
             time_precision: String::from("long"),
+            format: None,
+            date_format: None,
+            locale: None,
+            display: String::from("digits"),
+            mode: String::from("digital"),
+            latitude: None,
+            longitude: None,
         }
     }
 
     pub fn validate(&self) -> Result<(), String> {
-        let valid_formats = vec!["12-hour", "24-hour"];
+        let valid_formats = vec!["12-hour", "24-hour", "locale"];
         let valid_precisions = vec!["short", "long"];
+        let valid_modes = vec!["digital", "analog", "both"];
         let mut errors = Vec::new();
 
         if !valid_formats.contains(&self.clock_format.as_str()) {
@@ -32,6 +62,39 @@ impl GeneralConfig {
             errors.push(format!("Invalid time precision: {}", self.time_precision));
         }
 
+        if !valid_modes.contains(&self.mode.as_str()) {
+            errors.push(format!("Invalid mode: {}", self.mode));
+        }
+
+        if !DISPLAY_MODES.contains(&self.display.as_str()) {
+            errors.push(format!("Invalid display mode: {}", self.display));
+        }
+
+        if let Some(format) = &self.format {
+            if let Err(err) = validate_format(format) {
+                errors.push(format!("Invalid format: {}", err));
+            }
+            if let Err(err) = validate_placeholders(format, &self.placeholders()) {
+                errors.push(format!("Invalid format: {}", err));
+            }
+        }
+
+        match (self.latitude, self.longitude) {
+            (Some(latitude), Some(longitude)) => {
+                if let Err(err) = validate_coordinates(latitude, longitude) {
+                    errors.push(err);
+                }
+            }
+            (None, None) => {}
+            _ => errors.push("Latitude and longitude must be set together".to_string()),
+        }
+
+        if let Some(locale) = &self.locale {
+            if !locale_available(locale) {
+                errors.push(format!("Locale is not installed: {}", locale));
+            }
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -39,27 +102,73 @@ impl GeneralConfig {
         }
     }
 
-    pub fn start_clock_update(self, clock_label: Arc<Label>, state: Arc<Mutex<AppState>>) {
-        let general_config = self.clone();
-        glib::timeout_add_seconds_local(1, move || {
-            let current_time = general_config.get_current_time();
+    pub fn start_clock_update(
+        self,
+        clock_label: Arc<Label>,
+        state: Arc<Mutex<AppState>>,
+        ticker: &Ticker,
+    ) {
+        ticker.subscribe(move || {
+            let current_time = self.get_current_time();
             clock_label.set_label(&current_time);
             log_info(&state, &format!("Updated clock label to: {}", current_time));
-            Continue
         });
     }
 
-    pub fn get_current_time(&self) -> String {
-        let now = DateTime::now_local().expect("Failed to get local time");
+    // Resolve the time pattern, falling back to the clock_format/time_precision presets
+    pub fn time_format(&self) -> String {
+        if let Some(format) = &self.format {
+            return format.clone();
+        }
         match (self.clock_format.as_str(), self.time_precision.as_str()) {
-            ("24-hour", "short") => now.format("%H:%M").expect("Format failed").to_string(),
-            ("24-hour", "long") => now.format("%H:%M:%S").expect("Format failed").to_string(),
-            ("12-hour", "short") => now.format("%I:%M %p").expect("Format failed").to_string(),
-            ("12-hour", "long") => now
-                .format("%I:%M:%S %p")
-                .expect("Format failed")
-                .to_string(),
-            _ => now.format("%H:%M:%S").expect("Format failed").to_string(),
+            ("24-hour", "short") => "%H:%M",
+            ("24-hour", "long") => "%H:%M:%S",
+            ("12-hour", "short") => "%I:%M %p",
+            ("12-hour", "long") => "%I:%M:%S %p",
+            ("locale", "short") if uses_12_hour() => "%I:%M %p",
+            ("locale", "short") => "%H:%M",
+            ("locale", "long") => "%X",
+            _ => "%H:%M:%S",
+        }
+        .to_string()
+    }
+
+    pub fn shows_digital(&self) -> bool {
+        self.mode != "analog"
+    }
+
+    pub fn shows_analog(&self) -> bool {
+        self.mode != "digital"
+    }
+
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        self.latitude.zip(self.longitude)
+    }
+
+    pub fn placeholders(&self) -> Placeholders {
+        Placeholders {
+            coordinates: self.coordinates(),
         }
     }
+
+    // Formatter for the configured display mode, shared by the window and the bar output
+    pub fn formatter(&self) -> Box<dyn TimeFormatter> {
+        formatter_for(&self.display, &self.time_format(), &self.placeholders())
+    }
+
+    pub fn format_time(&self, time: &DateTime) -> String {
+        self.formatter().format(time)
+    }
+
+    pub fn get_current_time(&self) -> String {
+        let now = DateTime::now_local().expect("Failed to get local time");
+        self.format_time(&now)
+    }
+}
+
+// Default GeneralConfig
+impl Default for GeneralConfig {
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -0,0 +1,122 @@
+// src/configuration/holidays.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::config::get_config_dir;
+use crate::time::holidays::{
+    builtin_regions, builtin_rules, load_rule_file, HolidayCalendar, COUNTRIES,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct HolidaysConfig {
+    pub countries: Vec<String>, // "US", or a country and region such as "DE-BY"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>, // extra `[[holiday]]` rules
+    #[serde(skip)]
+    loaded: HolidayCalendar, // built once per config load, see load_calendar
+}
+
+impl HolidaysConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for entry in &self.countries {
+            let (country, region) = match entry.split_once('-') {
+                Some((country, region)) => (country, Some(region)),
+                None => (entry.as_str(), None),
+            };
+            if builtin_rules(country).is_none() {
+                return Err(format!(
+                    "Unknown country '{}', expected one of {}",
+                    entry,
+                    COUNTRIES.join(", ")
+                ));
+            }
+            if let Some(region) = region {
+                let regions = builtin_regions(country);
+                if !regions.iter().any(|r| r.eq_ignore_ascii_case(region)) {
+                    if regions.is_empty() {
+                        return Err(format!(
+                            "'{}' has no regional holidays, use '{}'",
+                            entry, country
+                        ));
+                    }
+                    return Err(format!(
+                        "Unknown region '{}', expected one of {}",
+                        entry,
+                        regions.join(", ")
+                    ));
+                }
+            }
+        }
+        self.build_calendar().map(|_| ())
+    }
+
+    // Function to resolve the user rule file, defaulting to one next to the config
+    pub fn rules_path(&self) -> Option<PathBuf> {
+        match &self.file {
+            Some(file) => match file.strip_prefix("~/") {
+                Some(rest) => dirs::home_dir().map(|home| home.join(rest)),
+                None => Some(PathBuf::from(file)),
+            },
+            None => {
+                let path = get_config_dir().join("hyprclock-holidays.toml");
+                path.exists().then_some(path)
+            }
+        }
+    }
+
+    // Function to build the holidays once when the config is loaded, instead of on every render
+    pub fn load_calendar(&mut self) -> Result<(), String> {
+        self.loaded = self.build_calendar()?;
+        Ok(())
+    }
+
+    // The holidays built by load_calendar; empty before that
+    pub fn calendar(&self) -> &HolidayCalendar {
+        &self.loaded
+    }
+
+    // Function to build the selected holidays, built-in sets first and user rules on top
+    fn build_calendar(&self) -> Result<HolidayCalendar, String> {
+        let custom = match self.rules_path() {
+            Some(path) => load_rule_file(&path)?,
+            None => Vec::new(),
+        };
+        HolidayCalendar::new(&self.countries, custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn countries(entries: &[&str]) -> HolidaysConfig {
+        HolidaysConfig {
+            countries: entries.iter().map(|entry| entry.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unknown_region_is_rejected() {
+        let err = countries(&["DE-XX"]).validate().unwrap_err();
+        assert!(err.contains("Unknown region 'DE-XX'"), "{}", err);
+        assert!(err.contains("BY"), "{}", err);
+    }
+
+    #[test]
+    fn region_without_rules_is_rejected() {
+        let err = countries(&["US-CA"]).validate().unwrap_err();
+        assert!(err.contains("use 'US'"), "{}", err);
+    }
+
+    #[test]
+    fn unknown_country_is_rejected() {
+        assert!(countries(&["XX"]).validate().is_err());
+    }
+}
@@ -1,11 +1,13 @@
 // src/logger.rs
 
+use crate::configuration::config::{get_cache_dir, get_state_dir};
 use fern::Dispatch;
 use gtk::Label;
 use gtk4 as gtk;
 use once_cell::sync::OnceCell;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use std::{env, error::Error, fs::File};
+use std::{error::Error, fs, fs::File};
 
 static LOGGER_INITIALIZED: OnceCell<bool> = OnceCell::new();
 
@@ -57,12 +59,10 @@ fn initialize_logger(
 pub fn setup_logging(state: &Arc<Mutex<AppState>>, debug: bool) -> Result<(), Box<dyn Error>> {
     log_info(state, "Setting up logging...");
 
-    let user_dir = env::var("HOME").unwrap_or_else(|_| "/home/default".to_string());
-    let log_file_path = if debug {
-        format!("{}/.config/hypr/hyprclock-debug.log", user_dir)
-    } else {
-        format!("{}/.config/hypr/hyprclock.log", user_dir)
-    };
+    let log_file_path = get_log_path(debug);
+    if let Some(parent) = log_file_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
 
     let log_level = if debug {
         log::LevelFilter::Debug
@@ -70,7 +70,16 @@ pub fn setup_logging(state: &Arc<Mutex<AppState>>, debug: bool) -> Result<(), Bo
         log::LevelFilter::Info
     };
 
-    initialize_logger(state, &log_file_path, log_level)
+    initialize_logger(state, &log_file_path.to_string_lossy(), log_level)
+}
+
+// Function to pick the log file: state for the normal log, cache for the disposable debug one
+pub fn get_log_path(debug: bool) -> PathBuf {
+    if debug {
+        get_cache_dir().join("hyprclock-debug.log")
+    } else {
+        get_state_dir().join("hyprclock.log")
+    }
 }
 
 // Create state
@@ -95,7 +104,6 @@ pub fn log_debug(state: &Arc<Mutex<AppState>>, message: &str) {
     update_log_label(state, message);
 }
 
-#[allow(dead_code)]
 pub fn log_warn(state: &Arc<Mutex<AppState>>, message: &str) {
     log::warn!("{}", message);
     update_log_label(state, message);
@@ -0,0 +1,45 @@
+// src/configuration/pomodoro.rs
+// github.com/cvusmo/hyprclock
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct PomodoroConfig {
+    pub enabled: bool,
+    pub work_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    pub cycles: u32, // work sessions before a long break
+    pub notify: bool,
+}
+
+impl PomodoroConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.work_minutes == 0 || self.short_break_minutes == 0 || self.long_break_minutes == 0 {
+            return Err("Pomodoro phases must be at least one minute.".to_string());
+        }
+        if self.cycles == 0 {
+            return Err("Pomodoro cycles must be at least 1.".to_string());
+        }
+        Ok(())
+    }
+}
+
+// Default PomodoroConfig
+impl Default for PomodoroConfig {
+    fn default() -> Self {
+        PomodoroConfig {
+            enabled: false,
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            cycles: 4,
+            notify: true,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+// src/configuration/stopwatch.rs
+// github.com/cvusmo/hyprclock
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct StopwatchConfig {
+    pub enabled: bool,
+    pub replace_clock: bool, // show the stopwatch in place of the clock instead of below it
+}
+
+impl StopwatchConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
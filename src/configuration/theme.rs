@@ -10,6 +10,18 @@ pub struct ThemeConfig {
     pub font_color: String,
     pub font_size: f32,
     pub scale_factor: f32,
+    #[serde(default)]
+    pub calendar: CalendarColors,
+}
+
+// Pango colors for the tooltip calendar, in the spirit of waybar's own clock calendar
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct CalendarColors {
+    pub today: String,
+    pub weekends: String,
+    pub events: String,  // holidays, moon phases and seasons
+    pub outside: String, // days of the previous and next month
 }
 
 impl ThemeConfig {
@@ -32,6 +44,21 @@ impl ThemeConfig {
             return Err("Invalid text color format. Must be a hex color code.".to_string());
         }
 
+        // Validate calendar color formats
+        for (name, color) in [
+            ("today", &self.calendar.today),
+            ("weekends", &self.calendar.weekends),
+            ("events", &self.calendar.events),
+            ("outside", &self.calendar.outside),
+        ] {
+            if !color.starts_with('#') || color.len() != 7 {
+                return Err(format!(
+                    "Invalid calendar {} color format. Must be a hex color code.",
+                    name
+                ));
+            }
+        }
+
         // Ensure font size is within a reasonable range
         if self.font_size <= 0.0 || self.font_size > 72.0 {
             return Err(
@@ -73,6 +100,19 @@ impl Default for ThemeConfig {
             font_color: "#F4E3C1".to_string(),
             font_size: 72.0,
             scale_factor: 1.0,
+            calendar: CalendarColors::default(),
+        }
+    }
+}
+
+// Default CalendarColors
+impl Default for CalendarColors {
+    fn default() -> Self {
+        CalendarColors {
+            today: "#F8D359".to_string(),
+            weekends: "#F87E59".to_string(),
+            events: "#59C2F8".to_string(),
+            outside: "#6E6A64".to_string(),
         }
     }
 }
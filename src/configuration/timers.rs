@@ -0,0 +1,37 @@
+// src/configuration/timers.rs
+// github.com/cvusmo/hyprclock
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TimersConfig {
+    pub notify: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>, // run with HYPRCLOCK_TIMER set when a timer finishes
+    pub presets: Vec<u32>, // minutes offered in the right-click menu
+}
+
+impl TimersConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.presets.contains(&0) {
+            return Err("Timer presets must be at least one minute.".to_string());
+        }
+        Ok(())
+    }
+}
+
+// Default TimersConfig
+impl Default for TimersConfig {
+    fn default() -> Self {
+        TimersConfig {
+            notify: true,
+            command: None,
+            presets: vec![5, 15, 25],
+        }
+    }
+}
@@ -2,8 +2,12 @@
 // github.com/cvusmo/hyprclock
 
 use crate::configuration::{
-    animation::AnimationConfig, env::EnvConfig, general::GeneralConfig, theme::ThemeConfig,
+    alarms::AlarmConfig, analog::AnalogConfig, animation::AnimationConfig,
+    calendar::CalendarConfig, date::DateConfig, env::EnvConfig, general::GeneralConfig,
+    holidays::HolidaysConfig, pomodoro::PomodoroConfig, theme::ThemeConfig, timers::TimersConfig,
+    waybar::WaybarConfig, zones::ZoneConfig,
 };
+use crate::time::placeholders::Placeholders;
 use std::collections::HashSet;
 
 // Validation function for animations
@@ -36,6 +40,34 @@ pub fn validate_animations(animations: &[AnimationConfig]) -> Result<(), String>
     Ok(())
 }
 
+// Validation function for alarms
+pub fn validate_alarms(alarms: &[AlarmConfig]) -> Result<(), String> {
+    let errors: Vec<String> = alarms
+        .iter()
+        .filter_map(|alarm| alarm.validate().err())
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Alarm validation failed: {}", errors.join("; ")))
+    }
+}
+
+// Validation function for the analog clock face
+pub fn validate_analog(analog: &AnalogConfig) -> Result<(), String> {
+    analog
+        .validate()
+        .map_err(|e| format!("Analog clock validation failed: {}", e))
+}
+
+// Validation function for the pomodoro cycle
+pub fn validate_pomodoro(pomodoro: &PomodoroConfig) -> Result<(), String> {
+    pomodoro
+        .validate()
+        .map_err(|e| format!("Pomodoro validation failed: {}", e))
+}
+
 // Validation function for theme
 pub fn validate_theme(theme: &ThemeConfig) -> Result<(), String> {
     theme
@@ -43,6 +75,33 @@ pub fn validate_theme(theme: &ThemeConfig) -> Result<(), String> {
         .map_err(|e| format!("Theme validation failed: {}", e))
 }
 
+// Validation function for countdown timers
+pub fn validate_timers(timers: &TimersConfig) -> Result<(), String> {
+    timers
+        .validate()
+        .map_err(|e| format!("Timers validation failed: {}", e))
+}
+
+// Validation function for the calendar grid
+pub fn validate_calendar(calendar: &CalendarConfig) -> Result<(), String> {
+    calendar
+        .validate()
+        .map_err(|e| format!("Calendar validation failed: {}", e))
+}
+
+// Validation function for the bar class and percentage rules
+pub fn validate_waybar(waybar: &WaybarConfig) -> Result<(), String> {
+    waybar
+        .validate()
+        .map_err(|e| format!("Waybar validation failed: {}", e))
+}
+
+// Validation function for the date line; placeholders come from [general]
+pub fn validate_date(date: &DateConfig, placeholders: &Placeholders) -> Result<(), String> {
+    date.validate(placeholders)
+        .map_err(|e| format!("Date validation failed: {}", e))
+}
+
 // Validation function for general configuration
 pub fn validate_general(general: &GeneralConfig) -> Result<(), String> {
     general
@@ -50,8 +109,29 @@ pub fn validate_general(general: &GeneralConfig) -> Result<(), String> {
         .map_err(|e| format!("General configuration validation failed: {}", e))
 }
 
+// Validation function for holiday sets and the user rule file
+pub fn validate_holidays(holidays: &HolidaysConfig) -> Result<(), String> {
+    holidays
+        .validate()
+        .map_err(|e| format!("Holidays validation failed: {}", e))
+}
+
 // Validation function for environment configuration
 pub fn validate_environment(env: &EnvConfig) -> Result<(), String> {
     env.validate()
         .map_err(|e| format!("Environment configuration validation failed: {}", e))
 }
+
+// Validation function for world clock zones; placeholders come from [general]
+pub fn validate_zones(zones: &[ZoneConfig], placeholders: &Placeholders) -> Result<(), String> {
+    let errors: Vec<String> = zones
+        .iter()
+        .filter_map(|zone| zone.validate(placeholders).err())
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Zone validation failed: {}", errors.join("; ")))
+    }
+}
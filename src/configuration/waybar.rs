@@ -0,0 +1,98 @@
+// src/configuration/waybar.rs
+// github.com/cvusmo/hyprclock
+
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+
+// Class rules the bar output understands
+pub const CLASS_RULES: [&str; 4] = ["night", "event-soon", "in-meeting", "alarm"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct WaybarConfig {
+    pub classes: Vec<String>, // rules from CLASS_RULES to evaluate on every update
+    pub event_soon_minutes: u32, // how close an event has to be for "event-soon"
+    pub night_start: String,  // HH:MM, used for "night" without latitude/longitude
+    pub night_end: String,
+    pub percentage: String, // none, day or workday
+    pub work_start: String, // HH:MM
+    pub work_end: String,
+}
+
+impl WaybarConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for class in &self.classes {
+            if !CLASS_RULES.contains(&class.as_str()) {
+                return Err(format!(
+                    "Unknown class rule '{}', expected one of {}",
+                    class,
+                    CLASS_RULES.join(", ")
+                ));
+            }
+        }
+
+        let valid_percentages = ["none", "day", "workday"];
+        if !valid_percentages.contains(&self.percentage.as_str()) {
+            return Err(format!(
+                "Invalid percentage '{}', expected none, day or workday",
+                self.percentage
+            ));
+        }
+
+        for time in [
+            &self.night_start,
+            &self.night_end,
+            &self.work_start,
+            &self.work_end,
+        ] {
+            if parse_time(time).is_none() {
+                return Err(format!("Invalid time '{}', expected HH:MM", time));
+            }
+        }
+        if parse_time(&self.work_start) >= parse_time(&self.work_end) {
+            return Err("work_start must be before work_end".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn uses(&self, class: &str) -> bool {
+        self.classes.iter().any(|c| c == class)
+    }
+
+    pub fn night(&self) -> (NaiveTime, NaiveTime) {
+        (
+            parse_time(&self.night_start).unwrap_or_default(),
+            parse_time(&self.night_end).unwrap_or_default(),
+        )
+    }
+
+    pub fn work_day(&self) -> (NaiveTime, NaiveTime) {
+        (
+            parse_time(&self.work_start).unwrap_or_default(),
+            parse_time(&self.work_end).unwrap_or_default(),
+        )
+    }
+}
+
+fn parse_time(time: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M").ok()
+}
+
+// Default WaybarConfig
+impl Default for WaybarConfig {
+    fn default() -> Self {
+        WaybarConfig {
+            classes: CLASS_RULES.iter().map(|class| class.to_string()).collect(),
+            event_soon_minutes: 15,
+            night_start: String::from("20:00"),
+            night_end: String::from("06:00"),
+            percentage: String::from("none"),
+            work_start: String::from("09:00"),
+            work_end: String::from("17:00"),
+        }
+    }
+}
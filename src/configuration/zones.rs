@@ -0,0 +1,61 @@
+// src/configuration/zones.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::format::validate_format;
+use crate::configuration::general::GeneralConfig;
+use crate::time::modes::{formatter_for, TimeFormatter};
+use crate::time::placeholders::{validate_placeholders, Placeholders};
+use glib::{DateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ZoneConfig {
+    pub name: String,  // IANA identifier, e.g. "America/New_York"
+    pub label: String, // name shown next to the time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>, // falls back to the general time format
+}
+
+impl ZoneConfig {
+    pub fn validate(&self, placeholders: &Placeholders) -> Result<(), String> {
+        if TimeZone::from_identifier(Some(&self.name)).is_none() {
+            return Err(format!(
+                "Unknown time zone '{}' for '{}'",
+                self.name, self.label
+            ));
+        }
+
+        if let Some(format) = &self.format {
+            validate_format(format)
+                .and_then(|_| validate_placeholders(format, placeholders))
+                .map_err(|e| format!("Invalid format for '{}': {}", self.label, e))?;
+        }
+
+        Ok(())
+    }
+
+    // Load the zone from the system tz database so DST rules are applied per instant
+    pub fn time_zone(&self) -> TimeZone {
+        TimeZone::from_identifier(Some(&self.name)).unwrap_or_else(TimeZone::utc)
+    }
+
+    pub fn now(&self) -> DateTime {
+        DateTime::now(&self.time_zone()).expect("Failed to get zone time")
+    }
+
+    // A zone format always means digits; otherwise follow the general display mode
+    pub fn formatter(&self, general: &GeneralConfig) -> Box<dyn TimeFormatter> {
+        match &self.format {
+            Some(format) => formatter_for("digits", format, &general.placeholders()),
+            None => general.formatter(),
+        }
+    }
+
+    pub fn format_time(&self, time: &DateTime, general: &GeneralConfig) -> String {
+        self.formatter(general).format(time)
+    }
+
+    pub fn get_current_time(&self, general: &GeneralConfig) -> String {
+        self.format_time(&self.now(), general)
+    }
+}
@@ -0,0 +1,310 @@
+// src/gui/alarms.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::alarms::AlarmConfig;
+use crate::configuration::config::Config;
+use crate::configuration::logger::{log_error, log_info, AppState};
+use crate::gui::menu::ContextMenu;
+use crate::time::tick::{TickUnit, Ticker};
+use crate::tools::alarms::{format_alarm, AlarmStore};
+use crate::tools::notify::{
+    connect_notification_actions, disconnect_notification_actions, send_notification,
+    send_urgent_notification,
+};
+use crate::tools::timers::now_unix;
+use chrono::Local;
+use gtk4::{
+    gio::SignalSubscriptionId, prelude::*, ApplicationWindow, Button, Dialog, DropDown, Entry,
+    Label,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+const RINGING_CLASS: &str = "alarm-ringing";
+
+thread_local! {
+    // Notification id to the alarm it announces, until it is answered. Kept outside the module
+    // so a notification sent before a config reload is still answered by the new one
+    static PENDING: RefCell<HashMap<u32, AlarmConfig>> = RefCell::new(HashMap::new());
+}
+
+pub struct AlarmsModule {
+    label: Label,
+    ticker: Ticker,
+    subscription: RefCell<Option<SignalSubscriptionId>>,
+    state: Arc<Mutex<AppState>>,
+}
+
+impl AlarmsModule {
+    pub fn new(config: &Config, state: &Arc<Mutex<AppState>>, clock_label: &Arc<Label>) -> Self {
+        let label = Label::builder()
+            .css_classes(vec!["next-alarm".to_string()])
+            .visible(false)
+            .build();
+
+        let clock_label_clone = Arc::clone(clock_label);
+        let state_clone = Arc::clone(state);
+        let result = connect_notification_actions(move |id, action| {
+            let Some(alarm) = PENDING.with_borrow_mut(|pending| pending.remove(&id)) else {
+                return;
+            };
+            let mut store = AlarmStore::load();
+            if action == "snooze" {
+                store.snooze(&alarm, now_unix());
+                log_info(
+                    &state_clone,
+                    &format!(
+                        "Snoozed alarm '{}' for {} min",
+                        alarm.label, alarm.snooze_minutes
+                    ),
+                );
+            } else {
+                store.stop_ringing(&alarm.label);
+                log_info(&state_clone, &format!("Dismissed alarm '{}'", alarm.label));
+            }
+            if !store.is_ringing() {
+                clock_label_clone.remove_css_class(RINGING_CLASS);
+            }
+            if let Err(err) = store.save() {
+                log_error(&state_clone, &format!("Failed to save alarms: {}", err));
+            }
+        });
+        let subscription = result.map_err(|err| log_error(state, &err)).ok();
+
+        let check: Rc<dyn Fn()> = {
+            let alarms = config.alarms.clone();
+            let label = label.clone();
+            let clock_label = Arc::clone(clock_label);
+            let state = Arc::clone(state);
+            Rc::new(move || {
+                let now = Local::now();
+                let mut store = AlarmStore::load();
+                let result = store.check(&alarms, now);
+
+                for alarm in &result.ringing {
+                    let body = format!("Alarm for {}", alarm.time);
+                    match send_urgent_notification(
+                        &alarm.label,
+                        &body,
+                        &[("snooze", "Snooze"), ("dismiss", "Dismiss")],
+                    ) {
+                        Ok(id) => {
+                            PENDING.with_borrow_mut(|pending| pending.insert(id, alarm.clone()));
+                        }
+                        Err(err) => log_error(&state, &err),
+                    }
+                    store.start_ringing(&alarm.label, now.timestamp());
+                    log_info(&state, &format!("Alarm '{}' ringing", alarm.label));
+                }
+
+                for (alarm, due) in &result.missed {
+                    let body = format!("Was due {}", due.format("%a %H:%M"));
+                    if let Err(err) =
+                        send_notification(&format!("Missed alarm: {}", alarm.label), &body, &[])
+                    {
+                        log_error(&state, &err);
+                    }
+                    log_info(
+                        &state,
+                        &format!("Missed alarm '{}' due {}", alarm.label, due.to_rfc3339()),
+                    );
+                }
+
+                if store.is_ringing() {
+                    clock_label.add_css_class(RINGING_CLASS);
+                } else {
+                    clock_label.remove_css_class(RINGING_CLASS);
+                }
+                Self::render(&label, &store, &alarms);
+
+                if let Err(err) = store.save() {
+                    log_error(&state, &format!("Failed to save alarms: {}", err));
+                }
+            })
+        };
+
+        // Alarms are set to the minute, whatever the clock itself shows
+        let ticker = Ticker::new(TickUnit::Minute);
+        let check_clone = Rc::clone(&check);
+        ticker.subscribe(move || check_clone());
+        ticker.start();
+        let ticker_clone = ticker.clone();
+        label.connect_destroy(move |_| ticker_clone.stop());
+
+        // Reports anything missed while hyprclock was not running
+        check();
+        log_info(state, "Alarms module initialized");
+
+        Self {
+            label,
+            ticker,
+            subscription: RefCell::new(subscription),
+            state: Arc::clone(state),
+        }
+    }
+
+    fn render(label: &Label, store: &AlarmStore, configured: &[AlarmConfig]) {
+        match store.next_alarm(configured, Local::now()) {
+            Some((alarm, due)) => {
+                label.set_label(&format!("⏰ {} {}", due.format("%a %H:%M"), alarm.label));
+                label.set_visible(true);
+            }
+            None => label.set_visible(false),
+        }
+    }
+
+    pub fn get_label(&self) -> &Label {
+        &self.label
+    }
+
+    // Function to stop checking once the window replaces this module, so alarms ring only once
+    // and answers go to the new module's handler
+    pub fn stop(&self) {
+        self.ticker.stop();
+        if let Some(subscription) = self.subscription.take() {
+            if let Err(err) = disconnect_notification_actions(subscription) {
+                log_error(&self.state, &err);
+            }
+        }
+    }
+
+    // Function to add alarm entries to the clock menu
+    pub fn fill_menu(&self, menu: &ContextMenu, config: &Config, state: &Arc<Mutex<AppState>>) {
+        let label = self.label.clone();
+        let alarms = config.alarms.clone();
+        let state_clone = Arc::clone(state);
+        menu.add_item("New alarm…", move |parent| {
+            Self::show_alarm_dialog(parent, &label, &alarms, &state_clone);
+        });
+
+        let label = self.label.clone();
+        let alarms = config.alarms.clone();
+        let state_clone = Arc::clone(state);
+        menu.add_item("Delete alarm…", move |parent| {
+            Self::show_delete_dialog(parent, &label, &alarms, &state_clone);
+        });
+    }
+
+    fn show_alarm_dialog(
+        parent: &Button,
+        label: &Label,
+        configured: &[AlarmConfig],
+        state: &Arc<Mutex<AppState>>,
+    ) {
+        let window = parent
+            .ancestor(ApplicationWindow::static_type())
+            .and_then(|w| w.downcast::<ApplicationWindow>().ok());
+        let dialog = Dialog::with_buttons(
+            Some("New Alarm"),
+            window.as_ref(),
+            gtk4::DialogFlags::MODAL,
+            &[
+                ("Save", gtk4::ResponseType::Accept),
+                ("Cancel", gtk4::ResponseType::Cancel),
+            ],
+        );
+        let content = dialog.content_area();
+        let time_entry = Entry::new();
+        time_entry.set_placeholder_text(Some("Time, e.g. 07:30"));
+        content.append(&time_entry);
+        let label_entry = Entry::new();
+        label_entry.set_placeholder_text(Some("Label"));
+        content.append(&label_entry);
+        let weekdays_entry = Entry::new();
+        weekdays_entry.set_placeholder_text(Some("Weekdays, e.g. mon,tue (empty for daily)"));
+        content.append(&weekdays_entry);
+
+        let label = label.clone();
+        let configured = configured.to_vec();
+        let state_clone = Arc::clone(state);
+        dialog.connect_response(move |dlg, response| {
+            if response == gtk4::ResponseType::Accept {
+                let time = time_entry.text().trim().to_string();
+                let name = match label_entry.text().trim() {
+                    "" => format!("Alarm {}", time),
+                    name => name.to_string(),
+                };
+                let weekdays: Vec<String> = weekdays_entry
+                    .text()
+                    .split(',')
+                    .map(|day| day.trim().to_lowercase())
+                    .filter(|day| !day.is_empty())
+                    .collect();
+
+                let alarm = AlarmConfig::new(&time, &name, weekdays);
+                match alarm.validate() {
+                    Ok(()) => {
+                        let mut store = AlarmStore::load();
+                        store.add(alarm);
+                        Self::persist(&store, &state_clone);
+                        Self::render(&label, &store, &configured);
+                        log_info(&state_clone, &format!("Added alarm '{}'", name));
+                    }
+                    Err(err) => log_error(&state_clone, &err),
+                }
+            }
+            dlg.close();
+        });
+
+        dialog.show();
+    }
+
+    fn show_delete_dialog(
+        parent: &Button,
+        label: &Label,
+        configured: &[AlarmConfig],
+        state: &Arc<Mutex<AppState>>,
+    ) {
+        let store = AlarmStore::load();
+        if store.alarms.is_empty() {
+            log_info(
+                state,
+                "No alarms to delete; alarms from the config file stay there",
+            );
+            return;
+        }
+
+        let window = parent
+            .ancestor(ApplicationWindow::static_type())
+            .and_then(|w| w.downcast::<ApplicationWindow>().ok());
+        let dialog = Dialog::with_buttons(
+            Some("Delete Alarm"),
+            window.as_ref(),
+            gtk4::DialogFlags::MODAL,
+            &[
+                ("Delete", gtk4::ResponseType::Accept),
+                ("Cancel", gtk4::ResponseType::Cancel),
+            ],
+        );
+        let names: Vec<String> = store.alarms.iter().map(format_alarm).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let choice = DropDown::from_strings(&names);
+        dialog.content_area().append(&choice);
+
+        let label = label.clone();
+        let configured = configured.to_vec();
+        let state_clone = Arc::clone(state);
+        dialog.connect_response(move |dlg, response| {
+            if response == gtk4::ResponseType::Accept {
+                let mut store = AlarmStore::load();
+                if let Some(alarm) = store.alarms.get(choice.selected() as usize).cloned() {
+                    store.remove(&alarm.label);
+                    Self::persist(&store, &state_clone);
+                    Self::render(&label, &store, &configured);
+                    log_info(&state_clone, &format!("Deleted alarm '{}'", alarm.label));
+                }
+            }
+            dlg.close();
+        });
+
+        dialog.show();
+    }
+
+    fn persist(store: &AlarmStore, state: &Arc<Mutex<AppState>>) {
+        if let Err(err) = store.save() {
+            log_error(state, &format!("Failed to save alarms: {}", err));
+        }
+    }
+}
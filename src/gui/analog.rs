@@ -0,0 +1,162 @@
+// src/gui/analog.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::analog::AnalogConfig;
+use crate::configuration::config::Config;
+use crate::configuration::logger::{log_error, log_info, AppState};
+use crate::time::tick::Ticker;
+use gtk4::{cairo, gdk::RGBA, prelude::*, DrawingArea};
+use std::f64::consts::PI;
+use std::sync::{Arc, Mutex};
+
+const ROMAN_NUMERALS: [&str; 12] = [
+    "XII", "I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X", "XI",
+];
+
+pub struct AnalogClock {
+    area: DrawingArea,
+}
+
+impl AnalogClock {
+    pub fn new(config: &Config, state: &Arc<Mutex<AppState>>) -> Self {
+        let area = DrawingArea::builder()
+            .content_width(200)
+            .content_height(200)
+            .hexpand(true)
+            .vexpand(true)
+            .css_classes(vec!["analog-clock".to_string()])
+            .build();
+
+        let face_color = parse_color(&config.theme.background_color);
+        let hand_color = parse_color(&config.theme.font_color);
+        let analog = config.analog.clone();
+        let state_clone = Arc::clone(state);
+        area.set_draw_func(move |_, cr, width, height| {
+            if let Err(err) = draw_face(cr, width, height, &analog, &face_color, &hand_color) {
+                log_error(
+                    &state_clone,
+                    &format!("Failed to draw analog clock: {}", err),
+                );
+            }
+        });
+
+        log_info(state, "Analog clock initialized");
+
+        Self { area }
+    }
+
+    // Redraw the face on every tick of the shared scheduler
+    pub fn start(&self, ticker: &Ticker) {
+        let area = self.area.clone();
+        ticker.subscribe(move || area.queue_draw());
+    }
+
+    pub fn get_area(&self) -> &DrawingArea {
+        &self.area
+    }
+}
+
+fn parse_color(hex: &str) -> RGBA {
+    RGBA::parse(hex).unwrap_or(RGBA::WHITE)
+}
+
+fn set_color(cr: &cairo::Context, color: &RGBA, alpha: f64) {
+    cr.set_source_rgba(
+        color.red() as f64,
+        color.green() as f64,
+        color.blue() as f64,
+        color.alpha() as f64 * alpha,
+    );
+}
+
+// Function to draw the face, scaled to the smaller side of the allocation
+fn draw_face(
+    cr: &cairo::Context,
+    width: i32,
+    height: i32,
+    analog: &AnalogConfig,
+    face_color: &RGBA,
+    hand_color: &RGBA,
+) -> Result<(), cairo::Error> {
+    let radius = (width.min(height) as f64 / 2.0) * 0.95;
+    let (cx, cy) = (width as f64 / 2.0, height as f64 / 2.0);
+
+    set_color(cr, face_color, 1.0);
+    cr.arc(cx, cy, radius, 0.0, 2.0 * PI);
+    cr.fill_preserve()?;
+    set_color(cr, hand_color, 1.0);
+    cr.set_line_width(radius * 0.03);
+    cr.stroke()?;
+
+    // Angles start at twelve o'clock and run clockwise
+    let point = |angle: f64, length: f64| {
+        (
+            cx + length * (angle - PI / 2.0).cos(),
+            cy + length * (angle - PI / 2.0).sin(),
+        )
+    };
+
+    if analog.tick_marks {
+        for i in 0..60 {
+            let angle = i as f64 * PI / 30.0;
+            let inner = if i % 5 == 0 { 0.85 } else { 0.92 };
+            let (x1, y1) = point(angle, radius * inner);
+            let (x2, y2) = point(angle, radius * 0.97);
+            cr.set_line_width(if i % 5 == 0 {
+                radius * 0.025
+            } else {
+                radius * 0.01
+            });
+            cr.move_to(x1, y1);
+            cr.line_to(x2, y2);
+            cr.stroke()?;
+        }
+    }
+
+    if analog.numerals != "none" {
+        cr.select_font_face("Sans", cairo::FontSlant::Normal, cairo::FontWeight::Bold);
+        cr.set_font_size(radius * 0.14);
+        for (hour, roman) in ROMAN_NUMERALS.iter().enumerate() {
+            let text = if analog.numerals == "roman" {
+                roman.to_string()
+            } else if hour == 0 {
+                "12".to_string()
+            } else {
+                hour.to_string()
+            };
+            let extents = cr.text_extents(&text)?;
+            let (x, y) = point(hour as f64 * PI / 6.0, radius * 0.72);
+            cr.move_to(
+                x - extents.width() / 2.0 - extents.x_bearing(),
+                y - extents.height() / 2.0 - extents.y_bearing(),
+            );
+            cr.show_text(&text)?;
+        }
+    }
+
+    let now = glib::DateTime::now_local().expect("Failed to get local time");
+    let seconds = now.second() as f64;
+    let minutes = now.minute() as f64 + seconds / 60.0;
+    let hours = (now.hour() % 12) as f64 + minutes / 60.0;
+
+    cr.set_line_cap(cairo::LineCap::Round);
+    let mut hands = vec![
+        (hours * PI / 6.0, radius * 0.5, radius * 0.06, 1.0),
+        (minutes * PI / 30.0, radius * 0.75, radius * 0.04, 1.0),
+    ];
+    if analog.second_hand {
+        hands.push((seconds * PI / 30.0, radius * 0.85, radius * 0.015, 0.7));
+    }
+    for (angle, length, line_width, alpha) in hands {
+        let (x, y) = point(angle, length);
+        set_color(cr, hand_color, alpha);
+        cr.set_line_width(line_width);
+        cr.move_to(cx, cy);
+        cr.line_to(x, y);
+        cr.stroke()?;
+    }
+
+    set_color(cr, hand_color, 1.0);
+    cr.arc(cx, cy, radius * 0.04, 0.0, 2.0 * PI);
+    cr.fill()
+}
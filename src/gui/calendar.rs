@@ -2,14 +2,17 @@
 use crate::configuration::config::Config;
 use crate::configuration::logger::{log_error, log_info, AppState};
 use crate::gui::clock::ClockModule;
-use chrono::Datelike;
-use glib::{DateTime, TimeZone};
+use crate::time::holidays::HolidayCalendar;
+use crate::time::tooltip::{describe_events, describe_holidays, generate_sun, month_grid};
+use crate::tools::events::{events_on, get_ics_path, load_events};
+use chrono::{Datelike, NaiveDate, Weekday};
+use glib::DateTime;
 use gtk4::{
-    prelude::*, ApplicationWindow, Button, Calendar, Dialog, Entry, Label as GtkLabel, Popover,
+    prelude::*, ApplicationWindow, Box as GtkBox, Button, Calendar, Dialog, Entry,
+    Label as GtkLabel, Orientation, Popover,
 };
 use icalendar::{Calendar as ICalendar, Component, Event, EventLike};
 use std::fs;
-use std::path::PathBuf;
 use std::process::Command;
 use std::sync::{Arc, Mutex};
 
@@ -18,18 +21,55 @@ pub struct CalendarModule {
     calendar: Calendar,
     popover: Popover,
     clock: Arc<ClockModule>,
+    holidays: HolidayCalendar,
 }
 
 impl CalendarModule {
     pub fn new(
         parent: &Button,
-        _config: &Config,
+        config: &Config,
         state: &Arc<Mutex<AppState>>,
         clock: Arc<ClockModule>,
     ) -> Self {
         let calendar = Calendar::new();
+        calendar.set_show_week_numbers(config.calendar.week_numbers);
+        let content = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .build();
+        content.append(&calendar);
+
+        let holidays = config.holidays.calendar().clone();
+
+        // Mark holidays, moon phases and seasons for whichever month is on display
+        let events_label = GtkLabel::builder()
+            .css_classes(vec!["calendar-events".to_string()])
+            .build();
+        content.append(&events_label);
+        Self::mark_events(&calendar, &events_label, &holidays);
+        let (events_label_clone, holidays_clone) = (events_label.clone(), holidays.clone());
+        calendar.connect_month_notify(move |cal| {
+            Self::mark_events(cal, &events_label_clone, &holidays_clone)
+        });
+        let holidays_clone = holidays.clone();
+        calendar
+            .connect_year_notify(move |cal| Self::mark_events(cal, &events_label, &holidays_clone));
+
+        // Sun times follow the selected day when a location is configured
+        let placeholders = config.general.placeholders();
+        if placeholders.coordinates.is_some() {
+            let sun_label = GtkLabel::builder()
+                .label(generate_sun(&placeholders, &calendar.date()))
+                .css_classes(vec!["calendar-sun".to_string()])
+                .build();
+            content.append(&sun_label);
+            calendar.connect_day_selected(move |cal| {
+                sun_label.set_label(&generate_sun(&placeholders, &cal.date()));
+            });
+        }
+
         let popover = Popover::builder()
-            .child(&calendar)
+            .child(&content)
             .autohide(true)
             .css_classes(vec!["calendar-popover".to_string()])
             .build();
@@ -43,6 +83,7 @@ impl CalendarModule {
             calendar: calendar.clone(),
             popover,
             clock,
+            holidays,
         };
         // Clone the module for use in the closure so that we do not move the value.
         let calendar_module_clone = calendar_module.clone();
@@ -63,63 +104,27 @@ impl CalendarModule {
         calendar_module
     }
 
-    pub fn show(&self) {
-        self.popover.popup();
+    fn mark_events(calendar: &Calendar, label: &GtkLabel, holidays: &HolidayCalendar) {
+        let (year, month) = (calendar.year(), calendar.month() as u32 + 1);
+        calendar.clear_marks();
+        // GTK lays out the weeks itself, so only the marks come from the grid
+        let events = load_events();
+        if let Some(grid) = month_grid(year, month, Weekday::Mon, holidays, &events) {
+            for day in grid.marked_days() {
+                calendar.mark_day(day);
+            }
+        }
+        let text = describe_holidays(holidays, year, month) + &describe_events(year, month);
+        label.set_label(text.trim_start());
     }
 
-    pub fn generate_tooltip(clock: &ClockModule) -> String {
-        let now = clock.get_current_datetime();
-        let year = now.year();
-        let month = now.month() as u32;
-        let day = now.day_of_month() as u32;
-        format!(
-            "<big>{} {}</big>\n<tt><small>{}</small></tt>",
-            year,
-            now.format("%B").expect("Format failed"),
-            Self::generate_calendar(month, year, day)
-        )
+    pub fn show(&self) {
+        self.popover.popup();
     }
 
-    fn generate_calendar(month: u32, year: i32, highlight_day: u32) -> String {
-        // Construct the first day as an ISO 8601 string and convert it.
-        let first_day_str = format!("{:04}-{:02}-{:02}T00:00:00Z", year, month, 1);
-        let first_day = DateTime::from_iso8601(&first_day_str, None).expect("Invalid date");
-        // Determine next month (adjusting for December)
-        let (next_year, next_month) = if month == 12 {
-            (year + 1, 1)
-        } else {
-            (year, month + 1)
-        };
-        let next_month_str = format!("{:04}-{:02}-{:02}T00:00:00Z", next_year, next_month, 1);
-        let next_month = DateTime::from_iso8601(&next_month_str, None).expect("Invalid date");
-        // Subtract one day (86400 seconds) from the start of next month by converting to a Unix timestamp.
-        let last_day_unix = next_month.to_unix() - 86400; // 86400 seconds in a day
-        let last_day = DateTime::from_unix_utc(last_day_unix).expect("Invalid date");
-        let days_in_month = last_day.day_of_month() as u32;
-
-        let mut calendar = String::new();
-        calendar.push_str("Mo Tu We Th Fr Sa Su\n");
-
-        // Get first day's weekday and adjust so week starts on Monday.
-        let weekday = (first_day.day_of_week() as u32) % 7; // assume 1=Mon, 7=Sun
-        let offset = (weekday + 6) % 7;
-        for _ in 0..offset {
-            calendar.push_str("   ");
-        }
-        let mut current_weekday = offset;
-        for d in 1..=days_in_month {
-            if d == highlight_day {
-                calendar.push_str(&format!("[{:2}] ", d));
-            } else {
-                calendar.push_str(&format!("{:2}  ", d));
-            }
-            current_weekday += 1;
-            if current_weekday == 7 {
-                calendar.push_str("\n");
-                current_weekday = 0;
-            }
-        }
-        calendar
+    // Popovers outlive their button unless detached, so a window rebuild calls this first
+    pub fn unparent(&self) {
+        self.popover.unparent();
     }
 
     fn show_schedule_dialog(&self, parent: &Button, date: DateTime, state: &Arc<Mutex<AppState>>) {
@@ -128,7 +133,17 @@ impl CalendarModule {
             .ancestor(gtk4::ApplicationWindow::static_type())
             .and_then(|w| w.downcast::<ApplicationWindow>().ok());
         if let Some(window) = window {
-            let events = Self::get_events_for_date(&date);
+            let mut events: Vec<String> = NaiveDate::from_ymd_opt(
+                date.year(),
+                date.month() as u32,
+                date.day_of_month() as u32,
+            )
+            .map(|day| self.holidays.on(day))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|holiday| format!("★ {}", holiday.name))
+            .collect();
+            events.extend(Self::get_events_for_date(&date));
             let dialog = Dialog::with_buttons(
                 Some(&format!(
                     "Schedule for {}-{}-{}",
@@ -218,60 +233,15 @@ impl CalendarModule {
     }
 
     fn get_events_for_date(date: &DateTime) -> Vec<String> {
-        let ics_path = Self::get_ics_path();
-        match fs::read_to_string(&ics_path) {
-            Ok(contents) => match contents.parse::<ICalendar>() {
-                Ok(calendar) => calendar
-                    .components
-                    .into_iter()
-                    .filter_map(|component| {
-                        component.as_event().and_then(|event| {
-                            event.get_start().and_then(|dtstart| {
-                                let dtstart_date = match dtstart {
-                                    icalendar::DatePerhapsTime::Date(d) => {
-                                        let iso_str = format!(
-                                            "{:04}-{:02}-{:02}",
-                                            d.year(),
-                                            d.month(),
-                                            d.day()
-                                        );
-                                        DateTime::from_iso8601(&iso_str, Some(&TimeZone::utc()))
-                                            .expect("Invalid date")
-                                    }
-                                    icalendar::DatePerhapsTime::DateTime(
-                                        icalendar::CalendarDateTime::Utc(dt),
-                                    ) => {
-                                        let iso_str = dt.to_rfc3339();
-                                        DateTime::from_iso8601(&iso_str, None)
-                                            .expect("Invalid datetime")
-                                    }
-                                    icalendar::DatePerhapsTime::DateTime(
-                                        icalendar::CalendarDateTime::Floating(_),
-                                    ) => {
-                                        return None; // Skip floating times
-                                    }
-                                    icalendar::DatePerhapsTime::DateTime(
-                                        icalendar::CalendarDateTime::WithTimezone { .. },
-                                    ) => {
-                                        return None; // Skip timezone-specific for now
-                                    }
-                                };
-                                if dtstart_date.year() == date.year()
-                                    && dtstart_date.month() == date.month()
-                                    && dtstart_date.day_of_month() == date.day_of_month()
-                                {
-                                    Some(event.get_summary().unwrap_or("Unnamed event").to_string())
-                                } else {
-                                    None
-                                }
-                            })
-                        })
-                    })
-                    .collect(),
-                Err(_) => vec![],
-            },
-            Err(_) => vec![],
-        }
+        let Some(day) =
+            NaiveDate::from_ymd_opt(date.year(), date.month() as u32, date.day_of_month() as u32)
+        else {
+            return Vec::new();
+        };
+        events_on(&load_events(), day)
+            .iter()
+            .map(|event| event.summary.clone())
+            .collect()
     }
 
     fn save_event(
@@ -280,7 +250,7 @@ impl CalendarModule {
         _state: &Arc<Mutex<AppState>>,
         clock: &Arc<ClockModule>,
     ) -> std::io::Result<()> {
-        let ics_path = Self::get_ics_path();
+        let ics_path = get_ics_path();
         let calendar = fs::read_to_string(&ics_path)
             .and_then(|contents| {
                 contents
@@ -314,10 +284,4 @@ impl CalendarModule {
             .spawn()
             .expect("Failed to launch Thunderbird");
     }
-
-    fn get_ics_path() -> PathBuf {
-        dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("/"))
-            .join(".thunderbird/calendar.ics")
-    }
 }
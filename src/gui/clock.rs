@@ -1,6 +1,7 @@
 // src/gui/clock.rs
 use crate::configuration::config::Config;
 use crate::configuration::logger::{log_info, AppState};
+use crate::time::tick::Ticker;
 use chrono::{DateTime as ChronoDateTime, Local, Utc};
 use glib::DateTime;
 use gtk4::Label;
@@ -23,24 +24,20 @@ impl ClockModule {
                 .build(),
         );
 
-        let config_clone = config.clone();
-        let label_clone = Arc::clone(&label);
-        let state_clone = Arc::clone(state);
-        glib::timeout_add_seconds_local(1, move || {
-            let current_time = config_clone.general.get_current_time();
-            label_clone.set_label(&current_time);
-            log_info(
-                &state_clone,
-                &format!("Updated clock label to: {}", current_time),
-            );
-            glib::ControlFlow::Continue
-        });
-
         log_info(state, "Clock module initialized");
 
         Self { label }
     }
 
+    // Keep the label current on every tick of the shared scheduler
+    pub fn start(&self, config: &Config, state: &Arc<Mutex<AppState>>, ticker: &Ticker) {
+        config.general.clone().start_clock_update(
+            Arc::clone(&self.label),
+            Arc::clone(state),
+            ticker,
+        );
+    }
+
     pub fn get_label(&self) -> &Arc<Label> {
         &self.label
     }
@@ -0,0 +1,43 @@
+// src/gui/date.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::config::Config;
+use crate::configuration::logger::{log_info, AppState};
+use crate::time::tick::Ticker;
+use gtk4::{pango, Label};
+use std::sync::{Arc, Mutex};
+
+pub struct DateModule {
+    label: Label,
+}
+
+impl DateModule {
+    pub fn new(config: &Config, state: &Arc<Mutex<AppState>>, ticker: &Ticker) -> Self {
+        let label = Label::builder()
+            .label(config.date.get_current_date(&config.general.placeholders()))
+            .justify(gtk4::Justification::Center)
+            .css_classes(vec!["date".to_string()])
+            .build();
+
+        // Size the line in points so it does not follow the clock's dynamic font size
+        let attributes = pango::AttrList::new();
+        attributes.insert(pango::AttrSize::new(
+            (config.date.font_size * pango::SCALE as f32) as i32,
+        ));
+        label.set_attributes(Some(&attributes));
+
+        let (date_config, placeholders) = (config.date.clone(), config.general.placeholders());
+        let label_clone = label.clone();
+        ticker.subscribe(move || {
+            label_clone.set_label(&date_config.get_current_date(&placeholders));
+        });
+
+        log_info(state, "Date module initialized");
+
+        Self { label }
+    }
+
+    pub fn get_label(&self) -> &Label {
+        &self.label
+    }
+}
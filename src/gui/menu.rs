@@ -0,0 +1,60 @@
+// src/gui/menu.rs
+// github.com/cvusmo/hyprclock
+
+use gtk4::{prelude::*, Box as GtkBox, Button, Orientation, Popover, Separator};
+
+// Right-click menu on the clock that modules add their entries to
+pub struct ContextMenu {
+    menu: GtkBox,
+    popover: Popover,
+    parent: Button,
+}
+
+impl ContextMenu {
+    pub fn new(parent: &Button) -> Self {
+        let menu = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(4)
+            .build();
+        let popover = Popover::builder()
+            .child(&menu)
+            .autohide(true)
+            .css_classes(vec!["clock-menu".to_string()])
+            .build();
+        popover.set_parent(parent);
+
+        let gesture = gtk4::GestureClick::builder().button(3).build();
+        let popover_clone = popover.clone();
+        gesture.connect_pressed(move |_, _, _, _| {
+            popover_clone.popup();
+        });
+        parent.add_controller(gesture);
+
+        Self {
+            menu,
+            popover,
+            parent: parent.clone(),
+        }
+    }
+
+    // Function to add an entry that closes the menu before running its action
+    pub fn add_item<F: Fn(&Button) + 'static>(&self, label: &str, action: F) {
+        let button = Button::with_label(label);
+        let popover = self.popover.clone();
+        let parent = self.parent.clone();
+        button.connect_clicked(move |_| {
+            popover.popdown();
+            action(&parent);
+        });
+        self.menu.append(&button);
+    }
+
+    pub fn add_separator(&self) {
+        self.menu.append(&Separator::new(Orientation::Horizontal));
+    }
+
+    // Function to detach the popover before its button goes away, e.g. on a config reload
+    pub fn unparent(&self) {
+        self.popover.unparent();
+    }
+}
@@ -0,0 +1,196 @@
+// src/gui/pomodoro.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::config::{get_state_dir, Config};
+use crate::configuration::logger::{log_error, log_info, AppState};
+use crate::configuration::pomodoro::PomodoroConfig;
+use crate::time::tick::{TickUnit, Ticker};
+use crate::tools::pomodoro::{collect_phase_changes, get_pomodoro_path, update_pomodoro, Pomodoro};
+use crate::tools::timers::now_unix;
+use gtk4::{gio, prelude::*, Box as GtkBox, Button, Label, Orientation, ProgressBar};
+use std::fs;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+pub struct PomodoroModule {
+    container: GtkBox,
+    ticker: Ticker,
+    monitor: Option<gio::FileMonitor>,
+}
+
+impl PomodoroModule {
+    pub fn new(config: &Config, state: &Arc<Mutex<AppState>>) -> Self {
+        let container = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .css_classes(vec!["pomodoro".to_string()])
+            .build();
+        let status = Label::builder()
+            .css_classes(vec!["pomodoro-status".to_string()])
+            .build();
+        let progress = ProgressBar::builder()
+            .css_classes(vec!["pomodoro-progress".to_string()])
+            .build();
+        let controls = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .halign(gtk4::Align::Center)
+            .build();
+        let toggle = Button::with_label("Start");
+        let skip = Button::with_label("Skip");
+        controls.append(&toggle);
+        controls.append(&skip);
+        container.append(&status);
+        container.append(&progress);
+        container.append(&controls);
+
+        // Like countdowns, the cycle gets its own second ticker that only runs while active
+        let ticker = Ticker::new(TickUnit::Second);
+        let refresh: Rc<dyn Fn()> = {
+            let widgets = (container.clone(), status, progress, toggle.clone());
+            let ticker = ticker.clone();
+            let pomodoro_config = config.pomodoro.clone();
+            let state = Arc::clone(state);
+            Rc::new(move || {
+                let (pomodoro, errors) = collect_phase_changes(&pomodoro_config);
+                Self::log_errors(&errors, &state);
+                let (container, status, progress, toggle) = &widgets;
+                Self::render(
+                    container,
+                    status,
+                    progress,
+                    toggle,
+                    &pomodoro,
+                    &pomodoro_config,
+                );
+                if pomodoro.is_running() {
+                    ticker.start();
+                } else {
+                    ticker.stop();
+                }
+            })
+        };
+
+        let refresh_clone = Rc::clone(&refresh);
+        ticker.subscribe(move || refresh_clone());
+
+        let (refresh_clone, pomodoro_config, state_clone) = (
+            Rc::clone(&refresh),
+            config.pomodoro.clone(),
+            Arc::clone(state),
+        );
+        toggle.connect_clicked(move |_| {
+            let (pomodoro, errors) = update_pomodoro(&pomodoro_config, |pomodoro| {
+                pomodoro.toggle(now_unix(), &pomodoro_config)
+            });
+            Self::log_errors(&errors, &state_clone);
+            if pomodoro.is_running() {
+                log_info(&state_clone, "Pomodoro started");
+            } else {
+                log_info(&state_clone, "Pomodoro stopped");
+            }
+            refresh_clone();
+        });
+
+        let (refresh_clone, pomodoro_config, state_clone) = (
+            Rc::clone(&refresh),
+            config.pomodoro.clone(),
+            Arc::clone(state),
+        );
+        skip.connect_clicked(move |_| {
+            let (_, errors) = update_pomodoro(&pomodoro_config, |pomodoro| {
+                pomodoro.skip(now_unix(), &pomodoro_config);
+            });
+            Self::log_errors(&errors, &state_clone);
+            refresh_clone();
+        });
+
+        // Pick up changes made from the CLI
+        if let Err(err) = fs::create_dir_all(get_state_dir()) {
+            log_error(state, &format!("Failed to create state directory: {}", err));
+        }
+        let pomodoro_file = gio::File::for_path(get_pomodoro_path());
+        let monitor =
+            match pomodoro_file.monitor_file(gio::FileMonitorFlags::NONE, gio::Cancellable::NONE) {
+                Ok(monitor) => {
+                    let refresh_clone = Rc::clone(&refresh);
+                    monitor.connect_changed(move |_, _, _, _| refresh_clone());
+                    // Keep the monitor alive as long as the widget
+                    let monitor_clone = monitor.clone();
+                    container.connect_destroy(move |_| {
+                        monitor_clone.cancel();
+                    });
+                    Some(monitor)
+                }
+                Err(err) => {
+                    log_error(state, &format!("Failed to watch pomodoro: {}", err));
+                    None
+                }
+            };
+
+        refresh();
+        log_info(state, "Pomodoro module initialized");
+
+        Self {
+            container,
+            ticker,
+            monitor,
+        }
+    }
+
+    fn render(
+        container: &GtkBox,
+        status: &Label,
+        progress: &ProgressBar,
+        toggle: &Button,
+        pomodoro: &Pomodoro,
+        config: &PomodoroConfig,
+    ) {
+        let now = now_unix();
+        for phase_class in [
+            "pomodoro-work",
+            "pomodoro-short-break",
+            "pomodoro-long-break",
+        ] {
+            container.remove_css_class(phase_class);
+        }
+
+        if pomodoro.is_running() {
+            container.add_css_class(pomodoro.phase.css_class());
+            status.set_label(&format!(
+                "{}  {}  ({}/{})",
+                pomodoro.phase.name(),
+                pomodoro.status(now),
+                pomodoro.completed,
+                config.cycles
+            ));
+            toggle.set_label("Stop");
+        } else {
+            status.set_label(&format!(
+                "{} {} min",
+                pomodoro.phase.name(),
+                config.work_minutes
+            ));
+            toggle.set_label("Start");
+        }
+        progress.set_fraction(pomodoro.progress(now));
+    }
+
+    pub fn get_container(&self) -> &GtkBox {
+        &self.container
+    }
+
+    // Function to stop refreshing once the window replaces this module, e.g. on a config reload
+    pub fn stop(&self) {
+        if let Some(monitor) = &self.monitor {
+            monitor.cancel();
+        }
+        self.ticker.stop();
+    }
+
+    fn log_errors(errors: &[String], state: &Arc<Mutex<AppState>>) {
+        for err in errors {
+            log_error(state, err);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+// src/gui/stopwatch.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::logger::{log_error, log_info, AppState};
+use crate::tools::stopwatch::{format_centiseconds, now_precise, Stopwatch};
+use gtk4::{prelude::*, Box as GtkBox, Button, Label, Orientation};
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+pub struct StopwatchModule {
+    container: GtkBox,
+}
+
+impl StopwatchModule {
+    pub fn new(state: &Arc<Mutex<AppState>>) -> Self {
+        let stopwatch = Rc::new(RefCell::new(Stopwatch::load()));
+
+        let container = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .css_classes(vec!["stopwatch".to_string()])
+            .build();
+        let display = Label::builder()
+            .label(format_centiseconds(
+                stopwatch.borrow().elapsed(now_precise()),
+            ))
+            .css_classes(vec!["stopwatch-time".to_string()])
+            .build();
+        let laps = Label::builder()
+            .css_classes(vec!["stopwatch-laps".to_string()])
+            .build();
+        let controls = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .halign(gtk4::Align::Center)
+            .build();
+
+        let toggle = Button::with_label("Start");
+        let lap = Button::with_label("Lap");
+        let reset = Button::with_label("Reset");
+        let export = Button::with_label("Export");
+        for button in [&toggle, &lap, &reset, &export] {
+            controls.append(button);
+        }
+        container.append(&display);
+        container.append(&controls);
+        container.append(&laps);
+
+        Self::render(&stopwatch.borrow(), &display, &laps, &toggle);
+        if stopwatch.borrow().is_running() {
+            Self::animate(&stopwatch, &display);
+        }
+
+        let (stopwatch_clone, display_clone, laps_clone) =
+            (Rc::clone(&stopwatch), display.clone(), laps.clone());
+        let state_clone = Arc::clone(state);
+        toggle.connect_clicked(move |toggle| {
+            let now = now_precise();
+            let running = stopwatch_clone.borrow().is_running();
+            if running {
+                stopwatch_clone.borrow_mut().stop(now);
+            } else {
+                stopwatch_clone.borrow_mut().start(now);
+                Self::animate(&stopwatch_clone, &display_clone);
+            }
+            Self::persist(&stopwatch_clone.borrow(), &state_clone);
+            Self::render(
+                &stopwatch_clone.borrow(),
+                &display_clone,
+                &laps_clone,
+                toggle,
+            );
+        });
+
+        let (stopwatch_clone, display_clone, laps_clone, toggle_clone) = (
+            Rc::clone(&stopwatch),
+            display.clone(),
+            laps.clone(),
+            toggle.clone(),
+        );
+        let state_clone = Arc::clone(state);
+        lap.connect_clicked(move |_| {
+            stopwatch_clone.borrow_mut().lap(now_precise());
+            Self::persist(&stopwatch_clone.borrow(), &state_clone);
+            Self::render(
+                &stopwatch_clone.borrow(),
+                &display_clone,
+                &laps_clone,
+                &toggle_clone,
+            );
+        });
+
+        let (stopwatch_clone, display_clone, laps_clone, toggle_clone) = (
+            Rc::clone(&stopwatch),
+            display.clone(),
+            laps.clone(),
+            toggle.clone(),
+        );
+        let state_clone = Arc::clone(state);
+        reset.connect_clicked(move |_| {
+            stopwatch_clone.borrow_mut().reset();
+            Self::persist(&stopwatch_clone.borrow(), &state_clone);
+            Self::render(
+                &stopwatch_clone.borrow(),
+                &display_clone,
+                &laps_clone,
+                &toggle_clone,
+            );
+        });
+
+        let stopwatch_clone = Rc::clone(&stopwatch);
+        let state_clone = Arc::clone(state);
+        export.connect_clicked(move |_| {
+            let path = get_export_path();
+            match stopwatch_clone.borrow().export_csv(&path) {
+                Ok(()) => log_info(
+                    &state_clone,
+                    &format!("Exported laps to {}", path.display()),
+                ),
+                Err(err) => log_error(&state_clone, &format!("Failed to export laps: {}", err)),
+            }
+        });
+
+        log_info(state, "Stopwatch module initialized");
+
+        Self { container }
+    }
+
+    pub fn get_container(&self) -> &GtkBox {
+        &self.container
+    }
+
+    // Redraw the display every frame while the stopwatch runs
+    fn animate(stopwatch: &Rc<RefCell<Stopwatch>>, display: &Label) {
+        let stopwatch = Rc::clone(stopwatch);
+        display.add_tick_callback(move |display, _| {
+            let stopwatch = stopwatch.borrow();
+            display.set_label(&format_centiseconds(stopwatch.elapsed(now_precise())));
+            if stopwatch.is_running() {
+                glib::ControlFlow::Continue
+            } else {
+                glib::ControlFlow::Break
+            }
+        });
+    }
+
+    fn render(stopwatch: &Stopwatch, display: &Label, laps: &Label, toggle: &Button) {
+        display.set_label(&format_centiseconds(stopwatch.elapsed(now_precise())));
+        toggle.set_label(if stopwatch.is_running() {
+            "Stop"
+        } else {
+            "Start"
+        });
+        let lap_lines: Vec<String> = stopwatch
+            .lap_times()
+            .iter()
+            .rev()
+            .map(|(number, lap, total)| {
+                format!(
+                    "Lap {}  {}  {}",
+                    number,
+                    format_centiseconds(*lap),
+                    format_centiseconds(*total)
+                )
+            })
+            .collect();
+        laps.set_label(&lap_lines.join("\n"));
+    }
+
+    fn persist(stopwatch: &Stopwatch, state: &Arc<Mutex<AppState>>) {
+        if let Err(err) = stopwatch.save() {
+            log_error(state, &format!("Failed to save stopwatch: {}", err));
+        }
+    }
+}
+
+// Laps are written next to the user's documents with a timestamped name
+fn get_export_path() -> PathBuf {
+    let directory = dirs::document_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| PathBuf::from("/tmp"));
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    directory.join(format!("hyprclock-laps-{}.csv", stamp))
+}
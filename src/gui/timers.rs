@@ -0,0 +1,186 @@
+// src/gui/timers.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::config::{get_state_dir, Config};
+use crate::configuration::logger::{log_error, log_info, AppState};
+use crate::gui::menu::ContextMenu;
+use crate::time::tick::{TickUnit, Ticker};
+use crate::tools::timers::{
+    collect_finished, format_remaining, format_timer, get_timers_path, now_unix, parse_duration,
+    TimerStore,
+};
+use gtk4::{
+    gio, prelude::*, ApplicationWindow, Box as GtkBox, Button, Dialog, Entry, Label, Orientation,
+};
+use std::fs;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+pub struct TimersModule {
+    container: GtkBox,
+    ticker: Ticker,
+    monitor: Option<gio::FileMonitor>,
+}
+
+impl TimersModule {
+    pub fn new(config: &Config, state: &Arc<Mutex<AppState>>) -> Self {
+        let container = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(4)
+            .css_classes(vec!["timers".to_string()])
+            .build();
+
+        // Countdowns need whole seconds, so they get their own ticker that only runs while needed
+        let ticker = Ticker::new(TickUnit::Second);
+        let refresh: Rc<dyn Fn()> = {
+            let container = container.clone();
+            let ticker = ticker.clone();
+            let timers_config = config.timers.clone();
+            let state = Arc::clone(state);
+            Rc::new(move || {
+                let (store, errors) = collect_finished(&timers_config);
+                for err in errors {
+                    log_error(&state, &err);
+                }
+                Self::render(&container, &store);
+                if store.timers.is_empty() {
+                    ticker.stop();
+                } else {
+                    ticker.start();
+                }
+            })
+        };
+
+        let refresh_clone = Rc::clone(&refresh);
+        ticker.subscribe(move || refresh_clone());
+
+        // Pick up timers created or cancelled from the CLI
+        if let Err(err) = fs::create_dir_all(get_state_dir()) {
+            log_error(state, &format!("Failed to create state directory: {}", err));
+        }
+        let timers_file = gio::File::for_path(get_timers_path());
+        let monitor =
+            match timers_file.monitor_file(gio::FileMonitorFlags::NONE, gio::Cancellable::NONE) {
+                Ok(monitor) => {
+                    let refresh_clone = Rc::clone(&refresh);
+                    monitor.connect_changed(move |_, _, _, _| refresh_clone());
+                    // Keep the monitor alive as long as the widget
+                    let monitor_clone = monitor.clone();
+                    container.connect_destroy(move |_| {
+                        monitor_clone.cancel();
+                    });
+                    Some(monitor)
+                }
+                Err(err) => {
+                    log_error(state, &format!("Failed to watch timers: {}", err));
+                    None
+                }
+            };
+
+        refresh();
+        log_info(state, "Timers module initialized");
+
+        Self {
+            container,
+            ticker,
+            monitor,
+        }
+    }
+
+    fn render(container: &GtkBox, store: &TimerStore) {
+        while let Some(child) = container.first_child() {
+            container.remove(&child);
+        }
+
+        let now = now_unix();
+        for timer in &store.timers {
+            let label = Label::builder()
+                .label(format_timer(timer, now))
+                .css_classes(vec!["timer".to_string()])
+                .build();
+            container.append(&label);
+        }
+    }
+
+    pub fn get_container(&self) -> &GtkBox {
+        &self.container
+    }
+
+    // Function to stop refreshing once the window replaces this module, e.g. on a config reload
+    pub fn stop(&self) {
+        if let Some(monitor) = &self.monitor {
+            monitor.cancel();
+        }
+        self.ticker.stop();
+    }
+
+    // Function to add timer presets and a custom timer entry to the clock menu
+    pub fn fill_menu(&self, menu: &ContextMenu, config: &Config, state: &Arc<Mutex<AppState>>) {
+        for minutes in &config.timers.presets {
+            let state_clone = Arc::clone(state);
+            let minutes = *minutes;
+            menu.add_item(&format!("{} min timer", minutes), move |_| {
+                Self::start_timer(
+                    &format!("{} min", minutes),
+                    minutes as i64 * 60,
+                    &state_clone,
+                );
+            });
+        }
+
+        let state_clone = Arc::clone(state);
+        menu.add_item("Custom timer…", move |parent| {
+            Self::show_timer_dialog(parent, &state_clone);
+        });
+    }
+
+    fn show_timer_dialog(parent: &Button, state: &Arc<Mutex<AppState>>) {
+        let window = parent
+            .ancestor(ApplicationWindow::static_type())
+            .and_then(|w| w.downcast::<ApplicationWindow>().ok());
+        let dialog = Dialog::with_buttons(
+            Some("New Timer"),
+            window.as_ref(),
+            gtk4::DialogFlags::MODAL,
+            &[
+                ("Start", gtk4::ResponseType::Accept),
+                ("Cancel", gtk4::ResponseType::Cancel),
+            ],
+        );
+        let content = dialog.content_area();
+        let name_entry = Entry::new();
+        name_entry.set_placeholder_text(Some("Timer name"));
+        content.append(&name_entry);
+        let duration_entry = Entry::new();
+        duration_entry.set_placeholder_text(Some("Duration, e.g. 10m or 1h30m"));
+        content.append(&duration_entry);
+
+        let state_clone = Arc::clone(state);
+        dialog.connect_response(move |dlg, response| {
+            if response == gtk4::ResponseType::Accept {
+                let name = name_entry.text().to_string();
+                match parse_duration(&duration_entry.text()) {
+                    Ok(duration) => {
+                        let name = if name.is_empty() {
+                            format_remaining(duration)
+                        } else {
+                            name
+                        };
+                        Self::start_timer(&name, duration, &state_clone);
+                    }
+                    Err(err) => log_error(&state_clone, &err),
+                }
+            }
+            dlg.close();
+        });
+
+        dialog.show();
+    }
+
+    fn start_timer(name: &str, duration: i64, state: &Arc<Mutex<AppState>>) {
+        match TimerStore::update(|store| store.add(name, duration, now_unix())) {
+            Ok(()) => log_info(state, &format!("Started timer '{}'", name)),
+            Err(err) => log_error(state, &format!("Failed to save timer '{}': {}", name, err)),
+        }
+    }
+}
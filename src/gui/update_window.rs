@@ -1,10 +1,34 @@
 // src/gui/update_window.rs
 // github.com/cvusmo/hyprclock
 
-use gtk::{gdk::Display, prelude::*, ApplicationWindow, Label};
+use crate::configuration::theme::ThemeConfig;
+use gtk::{gdk::Display, prelude::*, ApplicationWindow, CssProvider, Label};
 use gtk4 as gtk;
 use std::sync::Arc;
 
+// Function to attach the [theme] styles to the window's display; reloading the returned
+// provider restyles the window in place
+pub fn install_theme(window: &ApplicationWindow, theme: &ThemeConfig) -> CssProvider {
+    let provider = CssProvider::new();
+    apply_theme(&provider, theme);
+    gtk::style_context_add_provider_for_display(
+        &WidgetExt::display(window),
+        &provider,
+        gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
+    );
+    provider
+}
+
+// Function to replace the provider's styles with the given theme
+pub fn apply_theme(provider: &CssProvider, theme: &ThemeConfig) {
+    provider.load_from_data(&format!(
+        "window.window {{ background-color: {}; }}\n.clock {{ color: {}; font-size: {}pt; }}\n",
+        theme.background_color,
+        theme.font_color,
+        theme.font_size * theme.scale_factor
+    ));
+}
+
 // Function to monitor window resizing events
 pub fn monitor_css(window: &ApplicationWindow, clock_label: Arc<Label>) {
     // Clone necessary variables
@@ -1,15 +1,32 @@
 // src/gui/window.rs
-use crate::configuration::config::Config;
+use crate::configuration::config::{get_save_path, Config};
 use crate::configuration::logger::*;
+use crate::gui::alarms::AlarmsModule;
+use crate::gui::analog::AnalogClock;
 use crate::gui::calendar::CalendarModule;
 use crate::gui::clock::ClockModule;
-use crate::gui::update_window::monitor_css;
-use gtk4::{prelude::*, Application, ApplicationWindow, Button, Grid, Label};
+use crate::gui::date::DateModule;
+use crate::gui::menu::ContextMenu;
+use crate::gui::pomodoro::PomodoroModule;
+use crate::gui::stopwatch::StopwatchModule;
+use crate::gui::timers::TimersModule;
+use crate::gui::update_window::{apply_theme, install_theme, monitor_css};
+use crate::gui::zones::ZonesModule;
+use crate::time::locale::apply_locale;
+use crate::time::tick::Ticker;
+use gtk4::{
+    gio, prelude::*, Application, ApplicationWindow, Box as GtkBox, Button, CssProvider, Grid,
+    Label, Orientation, Widget,
+};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 
 pub fn build_ui(
     app: &Application,
     config: &Config,
+    config_path: &Path,
     state: &Arc<Mutex<AppState>>,
     debug_mode: bool,
 ) -> ApplicationWindow {
@@ -18,38 +35,251 @@ pub fn build_ui(
     let initial_width = 400;
     let initial_height = 200;
     let window = create_window(app, state, initial_width, initial_height);
+    let theme = install_theme(&window, &config.theme);
 
-    let clock_module = Arc::new(ClockModule::new(config, state));
-    let clock_label = clock_module.get_label();
-
-    let clock_button = Button::builder()
-        .child(&**clock_label)
-        .css_classes(vec!["clock-button".to_string()])
+    // The error line stays put while the content below it is swapped on reload
+    let layout = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(10)
+        .build();
+    let error_label = Label::builder()
+        .wrap(true)
+        .visible(false)
+        .css_classes(vec!["config-error".to_string()])
         .build();
+    layout.append(&error_label);
+
+    let content = WindowContent::new(config, state, debug_mode);
+    layout.append(&content.grid);
+    window.set_child(Some(&layout));
 
-    let calendar_module =
-        CalendarModule::new(&clock_button, config, state, Arc::clone(&clock_module));
+    monitor_css(&window, Arc::clone(&content.clock_label));
 
-    let debug_label = if debug_mode {
-        Some(create_debug_label())
-    } else {
-        None
+    let reload = ConfigReload {
+        layout,
+        error_label,
+        theme,
+        content: RefCell::new(content),
+        state: Arc::clone(state),
+        debug_mode,
     };
+    reload.watch(&window, config_path);
 
-    let grid = create_grid(&clock_button, debug_label.as_ref());
-    window.set_child(Some(&grid));
+    log_info(state, "Window built successfully.");
+    window
+}
 
-    monitor_css(&window, Arc::clone(clock_label));
+// Everything built from one config, so a reload can swap it out inside the same window
+struct WindowContent {
+    grid: Grid,
+    clock_label: Arc<Label>,
+    ticker: Ticker,
+    calendar: CalendarModule,
+    menu: ContextMenu,
+    timers: TimersModule,
+    alarms: AlarmsModule,
+    pomodoro: Option<PomodoroModule>,
+}
 
-    let gesture = gtk4::GestureClick::new();
-    let calendar_clone = calendar_module;
-    gesture.connect_pressed(move |_, _, _, _| {
-        calendar_clone.show();
-    });
-    clock_button.add_controller(gesture);
+impl WindowContent {
+    fn new(config: &Config, state: &Arc<Mutex<AppState>>, debug_mode: bool) -> Self {
+        let ticker = Ticker::for_config(config);
+        log_info(state, &format!("Clock ticks every {:?}", ticker.unit()));
 
-    log_info(state, "Window built successfully.");
-    window
+        let clock_module = Arc::new(ClockModule::new(config, state));
+        clock_module.start(config, state, &ticker);
+        let clock_label = Arc::clone(clock_module.get_label());
+
+        let analog_clock = if config.general.shows_analog() {
+            let analog_clock = AnalogClock::new(config, state);
+            analog_clock.start(&ticker);
+            Some(analog_clock)
+        } else {
+            None
+        };
+
+        let clock_face = create_clock_face(config, &clock_label, analog_clock.as_ref());
+        let clock_button = Button::builder()
+            .child(&clock_face)
+            .css_classes(vec!["clock-button".to_string()])
+            .build();
+
+        let date_module = if config.date.enabled {
+            Some(DateModule::new(config, state, &ticker))
+        } else {
+            None
+        };
+
+        let calendar_module =
+            CalendarModule::new(&clock_button, config, state, Arc::clone(&clock_module));
+
+        let zones_module = ZonesModule::new(config, state, &ticker);
+
+        let timers_module = TimersModule::new(config, state);
+        let alarms_module = AlarmsModule::new(config, state, &clock_label);
+
+        let menu = ContextMenu::new(&clock_button);
+        timers_module.fill_menu(&menu, config, state);
+        menu.add_separator();
+        alarms_module.fill_menu(&menu, config, state);
+
+        let pomodoro_module = if config.pomodoro.enabled {
+            Some(PomodoroModule::new(config, state))
+        } else {
+            None
+        };
+
+        let stopwatch_module = if config.stopwatch.enabled {
+            clock_button.set_visible(!config.stopwatch.replace_clock);
+            Some(StopwatchModule::new(state))
+        } else {
+            None
+        };
+
+        let debug_label = if debug_mode {
+            Some(create_debug_label())
+        } else {
+            None
+        };
+
+        // Rows below the clock, top to bottom; a disabled module keeps its row empty
+        let rows: [Option<&Widget>; 6] = [
+            date_module
+                .as_ref()
+                .map(|module| module.get_label().upcast_ref()),
+            Some(zones_module.get_container().upcast_ref()),
+            Some(timers_module.get_container().upcast_ref()),
+            Some(alarms_module.get_label().upcast_ref()),
+            pomodoro_module
+                .as_ref()
+                .map(|module| module.get_container().upcast_ref()),
+            stopwatch_module
+                .as_ref()
+                .map(|module| module.get_container().upcast_ref()),
+        ];
+        let grid = create_grid(&clock_button, &rows, debug_label.as_ref());
+
+        let gesture = gtk4::GestureClick::new();
+        let calendar_clone = calendar_module.clone();
+        gesture.connect_pressed(move |_, _, _, _| {
+            calendar_clone.show();
+        });
+        clock_button.add_controller(gesture);
+
+        ticker.start();
+
+        WindowContent {
+            grid,
+            clock_label,
+            ticker,
+            calendar: calendar_module,
+            menu,
+            timers: timers_module,
+            alarms: alarms_module,
+            pomodoro: pomodoro_module,
+        }
+    }
+
+    // Function to stop every timer and monitor of this content before it is dropped
+    fn stop(&self) {
+        self.ticker.stop();
+        self.timers.stop();
+        self.alarms.stop();
+        if let Some(pomodoro) = &self.pomodoro {
+            pomodoro.stop();
+        }
+        self.calendar.unparent();
+        self.menu.unparent();
+    }
+}
+
+// Watches the config file and rebuilds the window content from each valid version
+struct ConfigReload {
+    layout: GtkBox,
+    error_label: Label,
+    theme: CssProvider,
+    content: RefCell<WindowContent>,
+    state: Arc<Mutex<AppState>>,
+    debug_mode: bool,
+}
+
+impl ConfigReload {
+    fn watch(self, window: &ApplicationWindow, config_path: &Path) {
+        // A system-wide config gives way to the user's copy once that is saved, so watch both
+        let (config_path, user_path) = (config_path.to_path_buf(), get_save_path(config_path));
+        let mut watched = vec![config_path.clone()];
+        if user_path != config_path {
+            watched.push(user_path.clone());
+        }
+
+        let reload = Rc::new(self);
+        for watched_path in watched {
+            let config_file = gio::File::for_path(&watched_path);
+            let monitor = match config_file
+                .monitor_file(gio::FileMonitorFlags::NONE, gio::Cancellable::NONE)
+            {
+                Ok(monitor) => monitor,
+                Err(err) => {
+                    log_error(&reload.state, &format!("Failed to watch config: {}", err));
+                    continue;
+                }
+            };
+
+            let (reload, config_path, user_path) =
+                (Rc::clone(&reload), config_path.clone(), user_path.clone());
+            monitor.connect_changed(move |_, _, _, event| {
+                let path = if user_path.exists() {
+                    &user_path
+                } else {
+                    &config_path
+                };
+                // Both rewrites and replaced files end with this hint; a missing file would make
+                // load_config write the defaults over it
+                if event == gio::FileMonitorEvent::ChangesDoneHint && path.exists() {
+                    reload.reload(path);
+                }
+            });
+            // Keep the monitor alive as long as the window
+            window.connect_destroy(move |_| {
+                monitor.cancel();
+            });
+        }
+    }
+
+    // Function to swap in the new config, or keep the running one and show why it was rejected
+    fn reload(&self, path: &Path) {
+        let config = match Config::load_config(Some(path.to_string_lossy().into_owned())) {
+            Ok(config) => config,
+            Err(err) => {
+                log_error(
+                    &self.state,
+                    &format!("Keeping the previous config: {}", err),
+                );
+                self.error_label.set_markup(&format!(
+                    "<b>Config not applied:</b> {}",
+                    glib::markup_escape_text(&err.to_string())
+                ));
+                self.error_label.set_visible(true);
+                return;
+            }
+        };
+        self.error_label.set_visible(false);
+        for warning in &config.warnings {
+            log_warn(&self.state, warning);
+        }
+
+        if let Err(err) = apply_locale(config.general.locale.as_deref()) {
+            log_error(&self.state, &format!("Failed to apply locale: {}", err));
+        }
+        apply_theme(&self.theme, &config.theme);
+
+        let content = WindowContent::new(&config, &self.state, self.debug_mode);
+        let previous = self.content.replace(content);
+        previous.stop();
+        self.layout.remove(&previous.grid);
+        self.layout.append(&self.content.borrow().grid);
+        log_info(&self.state, "Config reloaded");
+    }
 }
 
 fn create_window(
@@ -69,6 +299,28 @@ fn create_window(
         .build()
 }
 
+// Function to combine the digital label and the analog face according to `mode`
+fn create_clock_face(
+    config: &Config,
+    clock_label: &Arc<Label>,
+    analog_clock: Option<&AnalogClock>,
+) -> Widget {
+    match analog_clock {
+        Some(analog_clock) => {
+            let face = GtkBox::builder()
+                .orientation(Orientation::Vertical)
+                .spacing(10)
+                .build();
+            face.append(analog_clock.get_area());
+            if config.general.shows_digital() {
+                face.append(&**clock_label);
+            }
+            face.upcast()
+        }
+        None => clock_label.as_ref().clone().upcast(),
+    }
+}
+
 fn create_debug_label() -> Arc<Label> {
     Arc::new(
         Label::builder()
@@ -78,11 +330,21 @@ fn create_debug_label() -> Arc<Label> {
     )
 }
 
-fn create_grid(clock_button: &Button, debug_label: Option<&Arc<Label>>) -> Grid {
+fn create_grid(
+    clock_button: &Button,
+    rows: &[Option<&Widget>],
+    debug_label: Option<&Arc<Label>>,
+) -> Grid {
     let grid = Grid::builder().row_spacing(10).column_spacing(10).build();
 
     grid.attach(clock_button, 0, 1, 2, 1);
 
+    for (row, widget) in (2..).zip(rows) {
+        if let Some(widget) = widget {
+            grid.attach(*widget, 0, row, 2, 1);
+        }
+    }
+
     if let Some(label) = debug_label {
         grid.attach(label.as_ref(), 0, 0, 2, 1);
         label.set_hexpand(true);
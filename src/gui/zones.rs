@@ -0,0 +1,66 @@
+// src/gui/zones.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::config::Config;
+use crate::configuration::logger::{log_info, AppState};
+use crate::time::tick::Ticker;
+use gtk4::{prelude::*, Box as GtkBox, Label, Orientation};
+use std::sync::{Arc, Mutex};
+
+pub struct ZonesModule {
+    container: GtkBox,
+}
+
+impl ZonesModule {
+    pub fn new(config: &Config, state: &Arc<Mutex<AppState>>, ticker: &Ticker) -> Self {
+        let container = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(4)
+            .css_classes(vec!["zones".to_string()])
+            .build();
+
+        let mut rows = Vec::new();
+        for zone in &config.zones {
+            let row = GtkBox::builder()
+                .orientation(Orientation::Horizontal)
+                .spacing(12)
+                .css_classes(vec!["zone".to_string()])
+                .build();
+            let name_label = Label::builder()
+                .label(&zone.label)
+                .hexpand(true)
+                .xalign(0.0)
+                .css_classes(vec!["zone-label".to_string()])
+                .build();
+            let time_label = Label::builder()
+                .label(zone.get_current_time(&config.general))
+                .xalign(1.0)
+                .css_classes(vec!["zone-time".to_string()])
+                .build();
+            row.append(&name_label);
+            row.append(&time_label);
+            container.append(&row);
+            rows.push((zone.clone(), time_label));
+        }
+
+        if !rows.is_empty() {
+            let general_clone = config.general.clone();
+            ticker.subscribe(move || {
+                for (zone, time_label) in &rows {
+                    time_label.set_label(&zone.get_current_time(&general_clone));
+                }
+            });
+        }
+
+        log_info(
+            state,
+            &format!("Zones module initialized with {} zones", config.zones.len()),
+        );
+
+        Self { container }
+    }
+
+    pub fn get_container(&self) -> &GtkBox {
+        &self.container
+    }
+}
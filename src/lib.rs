@@ -1,19 +1,61 @@
 pub mod configuration {
+    pub mod alarms;
+    pub mod analog;
     pub mod animation;
+    pub mod calendar;
     pub mod config;
+    pub mod date;
     pub mod env;
     pub mod flags;
+    pub mod format;
     pub mod general;
+    pub mod holidays;
     pub mod logger;
+    pub mod pomodoro;
+    pub mod stopwatch;
     pub mod theme;
+    pub mod timers;
     pub mod validate;
+    pub mod waybar;
+    pub mod zones;
 }
 pub mod debug {
     pub mod debug;
 }
 pub mod gui {
+    pub mod alarms;
+    pub mod analog;
     pub mod calendar;
     pub mod clock;
+    pub mod date;
+    pub mod menu;
+    pub mod pomodoro;
+    pub mod stopwatch;
+    pub mod timers;
     pub mod update_window;
     pub mod window;
+    pub mod zones;
+}
+pub mod time {
+    pub mod astronomy;
+    pub mod grid;
+    pub mod holidays;
+    pub mod locale;
+    pub mod modes;
+    pub mod placeholders;
+    pub mod sun;
+    pub mod tick;
+    pub mod tooltip;
+}
+pub mod tools {
+    pub mod alarms;
+    pub mod bar;
+    pub mod calendar;
+    pub mod events;
+    pub mod lock;
+    pub mod notify;
+    pub mod pomodoro;
+    pub mod stopwatch;
+    pub mod timers;
+    pub mod waybar;
 }
@@ -1,36 +1,69 @@
 use clap::Parser;
 use gtk4::{glib, prelude::*, Application};
 use hyprclock::configuration::{
-    config::Config,
+    config::{get_config_path, get_save_path, Config},
     flags::Args,
-    logger::{create_state, log_debug, log_error, log_info, setup_logging, AppState},
+    logger::{
+        create_state, get_log_path, log_debug, log_error, log_info, log_warn, setup_logging,
+        AppState,
+    },
 };
 use hyprclock::debug::debug::enable_debug_mode;
-use hyprclock::gui::calendar::CalendarModule;
-use hyprclock::gui::clock::ClockModule;
 use hyprclock::gui::window::build_ui;
-use serde_json::json;
+use hyprclock::time::locale::apply_locale;
+use hyprclock::time::tick::TickUnit;
+use hyprclock::tools::bar::{backend_for, BarBackend, BarStatus};
+use hyprclock::tools::calendar::CalendarView;
+use hyprclock::tools::events::{get_ics_path, load_events};
+use hyprclock::tools::pomodoro::{daily_totals, update_pomodoro};
+use hyprclock::tools::timers::{now_unix, parse_duration, TimerStore};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::sync::{Arc, Mutex};
+use std::thread;
 
 const APP_ID: &str = "org.cvusmo.Hyprclock";
 
 fn main() -> glib::ExitCode {
-    let _gtk_init = gtk4::init();
     let args = Args::parse(); // Use the clap-provided parse method.
-    let state = create_state();
 
-    if args.waybar {
+    if args.timer.is_some() || args.timer_cancel.is_some() {
+        return run_timer_command(args.timer, args.timer_cancel);
+    }
+
+    if args.pomodoro.is_some() || args.pomodoro_history {
         let config = Config::load_config(args.config).unwrap_or_else(|_| Config::new());
-        let clock_module = ClockModule::new(&config, &state);
-        // Build JSON using serde_json to properly escape any control characters.
-        let output = json!({
-            "text": clock_module.get_time(&config),
-            "tooltip": CalendarModule::generate_tooltip(&clock_module)
-        });
-        println!("{}", output.to_string());
+        return run_pomodoro_command(&config, args.pomodoro, args.pomodoro_history);
+    }
+
+    if let Some(action) = args.waybar_action {
+        return run_waybar_action(&action);
+    }
+
+    // Bar output never touches GTK, so it also runs without a display
+    let bar = args
+        .bar
+        .or_else(|| args.waybar.then(|| "waybar".to_string()));
+    if let Some(bar) = bar {
+        let backend = backend_for(&bar);
+        if args.stream || backend.streams() {
+            return run_bar_stream(args.config, backend);
+        }
+        let config = Config::load_config(args.config).unwrap_or_else(|_| Config::new());
+        let _ = apply_locale(config.general.locale.as_deref());
+        println!(
+            "{}",
+            backend.render(&BarStatus::collect(&config, &load_events()))
+        );
         return glib::ExitCode::SUCCESS;
     }
 
+    // Only the window needs GTK and the log label
+    let _gtk_init = gtk4::init();
+    let state = create_state();
+
     if let Err(err) = setup_logging(&state, args.debug) {
         eprintln!("Failed to setup logging: {}", err);
         return glib::ExitCode::FAILURE;
@@ -47,9 +80,20 @@ fn main() -> glib::ExitCode {
 
     if args.log {
         eprintln!("Log mode enabled.");
-        log_info(&state, "Log mode is enabled. Log is located at: TODO");
+        log_info(
+            &state,
+            &format!(
+                "Log mode is enabled. Log is located at: {}",
+                get_log_path(args.debug).display()
+            ),
+        );
     }
 
+    let config_path = args
+        .config
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(get_config_path);
     let config = match Config::load_config(args.config) {
         Ok(cfg) => cfg,
         Err(err) => {
@@ -57,13 +101,214 @@ fn main() -> glib::ExitCode {
             Config::new()
         }
     };
+    for warning in &config.warnings {
+        log_warn(&state, warning);
+    }
+
+    if let Err(err) = apply_locale(config.general.locale.as_deref()) {
+        log_error(&state, &format!("Failed to apply locale: {}", err));
+    }
+
+    create_and_run_app(&state, config, config_path, args.debug)
+}
 
-    create_and_run_app(&state, config, args.debug)
+// Keep running for the bar: print an update whenever the output changes, act on clicks, and
+// pick up config changes without a restart
+fn run_bar_stream(config_file: Option<String>, backend: Box<dyn BarBackend>) -> glib::ExitCode {
+    let config_path = config_file
+        .map(PathBuf::from)
+        .unwrap_or_else(get_config_path);
+    // A system-wide config gives way to the user's copy once that is saved
+    let user_path = get_save_path(&config_path);
+    let current_path = || {
+        if user_path.exists() {
+            user_path.clone()
+        } else {
+            config_path.clone()
+        }
+    };
+    let load = || {
+        let config = Config::load_config(Some(current_path().to_string_lossy().into_owned()));
+        if let Ok(config) = &config {
+            for warning in &config.warnings {
+                eprintln!("Warning: {}", warning);
+            }
+            let _ = apply_locale(config.general.locale.as_deref());
+        }
+        config
+    };
+    let modified = || {
+        fs::metadata(current_path())
+            .and_then(|meta| meta.modified())
+            .ok()
+    };
+
+    // Click events are read on their own thread so they can cut the wait for the next tick
+    let (sender, clicks) = mpsc::channel::<String>();
+    if backend.reads_clicks() {
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    } else {
+        drop(sender);
+    }
+
+    // The ICS file is parsed again only when it changes, like the config
+    let events_modified = || {
+        fs::metadata(get_ics_path())
+            .and_then(|meta| meta.modified())
+            .ok()
+    };
+
+    let mut config = load().unwrap_or_else(|_| Config::new());
+    let mut last_modified = modified();
+    let mut events = load_events();
+    let mut last_events_modified = events_modified();
+    let mut last_line = String::new();
+    let mut stdout = io::stdout();
+
+    if let Some(header) = backend.header() {
+        if writeln!(stdout, "{}", header).is_err() {
+            return glib::ExitCode::SUCCESS;
+        }
+    }
+
+    loop {
+        let current = modified();
+        if current != last_modified {
+            last_modified = current;
+            match load() {
+                Ok(reloaded) => config = reloaded,
+                Err(err) => eprintln!("Keeping the previous config: {}", err),
+            }
+        }
+
+        let current = events_modified();
+        if current != last_events_modified {
+            last_events_modified = current;
+            events = load_events();
+        }
+
+        let status = BarStatus::collect(&config, &events);
+        let line = backend.render(&status);
+        if line != last_line {
+            // The bar closing the pipe ends the stream
+            if writeln!(stdout, "{}", line)
+                .and_then(|_| stdout.flush())
+                .is_err()
+            {
+                return glib::ExitCode::SUCCESS;
+            }
+            last_line = line;
+        }
+
+        // Timers, the stopwatch and the pomodoro change by the second; otherwise wait for the
+        // clock's own unit, at most a minute so meetings, alarms and new timers show up in time
+        let unit = if status.live {
+            TickUnit::Second
+        } else {
+            config.general.formatter().tick_unit().min(TickUnit::Minute)
+        };
+        let now = glib::DateTime::now_local().expect("Failed to get local time");
+        let delay = unit.delay_until_next(&now);
+        match clicks.recv_timeout(delay) {
+            Ok(click) => backend.handle_input(&click, &config),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => thread::sleep(delay),
+        }
+    }
+}
+
+fn run_timer_command(timer: Option<Vec<String>>, cancel: Option<String>) -> glib::ExitCode {
+    let duration = match timer.as_ref().map(|values| parse_duration(&values[1])) {
+        Some(Err(err)) => {
+            eprintln!("{}", err);
+            return glib::ExitCode::FAILURE;
+        }
+        Some(Ok(duration)) => Some(duration),
+        None => None,
+    };
+
+    let result = TimerStore::update(|store| {
+        if let Some(name) = &cancel {
+            if !store.cancel(name) {
+                return Err(format!("No running timer named '{}'", name));
+            }
+        }
+        if let (Some(values), Some(duration)) = (&timer, duration) {
+            store.add(&values[0], duration, now_unix());
+        }
+        Ok(())
+    });
+
+    match result {
+        Ok(Ok(())) => glib::ExitCode::SUCCESS,
+        Ok(Err(err)) => {
+            eprintln!("{}", err);
+            glib::ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("Failed to save timers: {}", err);
+            glib::ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_waybar_action(action: &str) -> glib::ExitCode {
+    let mut view = CalendarView::load();
+    if let Err(err) = view.apply(action) {
+        eprintln!("{}", err);
+        return glib::ExitCode::FAILURE;
+    }
+    if let Err(err) = view.save() {
+        eprintln!("Failed to save calendar view: {}", err);
+        return glib::ExitCode::FAILURE;
+    }
+    glib::ExitCode::SUCCESS
+}
+
+fn run_pomodoro_command(config: &Config, action: Option<String>, history: bool) -> glib::ExitCode {
+    if let Some(action) = action {
+        let (_, errors) = update_pomodoro(&config.pomodoro, |pomodoro| match action.as_str() {
+            "start" => pomodoro.start(now_unix(), &config.pomodoro),
+            "stop" => pomodoro.stop(),
+            "toggle" => pomodoro.toggle(now_unix(), &config.pomodoro),
+            _ => {
+                pomodoro.skip(now_unix(), &config.pomodoro);
+            }
+        });
+        if !errors.is_empty() {
+            for err in errors {
+                eprintln!("{}", err);
+            }
+            return glib::ExitCode::FAILURE;
+        }
+    }
+
+    if history {
+        match daily_totals() {
+            Ok(totals) => {
+                for (day, minutes) in totals {
+                    println!("{}  {}h{:02}m", day, minutes / 60, minutes % 60);
+                }
+            }
+            Err(err) => {
+                eprintln!("Failed to read pomodoro history: {}", err);
+                return glib::ExitCode::FAILURE;
+            }
+        }
+    }
+    glib::ExitCode::SUCCESS
 }
 
 fn create_and_run_app(
     state: &Arc<Mutex<AppState>>,
     config: Config,
+    config_path: PathBuf,
     debug_mode: bool,
 ) -> glib::ExitCode {
     log_info(state, "Creating application...");
@@ -74,7 +319,7 @@ fn create_and_run_app(
     app.connect_activate(move |app| {
         log_info(&state_clone, "Application activated...");
         log_info(&state_clone, "Building the main UI...");
-        let window = build_ui(app, &config, &state_clone, debug_mode);
+        let window = build_ui(app, &config, &config_path, &state_clone, debug_mode);
         window.present();
     });
 
@@ -0,0 +1,360 @@
+// src/time/astronomy.rs
+// github.com/cvusmo/hyprclock
+
+// Moon phases and the year's equinoxes and solstices, after Meeus, "Astronomical Algorithms"
+// (chapters 27 and 49). Only the main periodic terms are kept, which is good to a few minutes.
+
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+use std::f64::consts::PI;
+
+const SYNODIC_MONTH: f64 = 29.530588861; // days
+const UNIX_EPOCH_JULIAN: f64 = 2440587.5;
+const DELTA_T: f64 = 69.0; // seconds between dynamical time and UTC, close enough this century
+
+// Periodic terms for the equinoxes and solstices: amplitude, phase, rate
+const SEASON_TERMS: [(f64, f64, f64); 24] = [
+    (485.0, 324.96, 1934.136),
+    (203.0, 337.23, 32964.467),
+    (199.0, 342.08, 20.186),
+    (182.0, 27.85, 445267.112),
+    (156.0, 73.14, 45036.886),
+    (136.0, 171.52, 22518.443),
+    (77.0, 222.54, 65928.934),
+    (74.0, 296.72, 3034.906),
+    (70.0, 243.58, 9037.513),
+    (58.0, 119.81, 33718.147),
+    (52.0, 297.17, 150.678),
+    (50.0, 21.02, 2281.226),
+    (45.0, 247.54, 29929.562),
+    (44.0, 325.15, 31555.956),
+    (29.0, 60.93, 4443.417),
+    (18.0, 155.12, 67555.328),
+    (17.0, 288.79, 4562.452),
+    (16.0, 198.04, 62894.029),
+    (14.0, 199.76, 31436.921),
+    (12.0, 95.39, 14577.848),
+    (12.0, 287.11, 31931.756),
+    (12.0, 320.81, 34777.259),
+    (9.0, 227.73, 1222.114),
+    (8.0, 15.45, 16859.074),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Season {
+    MarchEquinox,
+    JuneSolstice,
+    SeptemberEquinox,
+    DecemberSolstice,
+}
+
+impl Season {
+    pub const ALL: [Season; 4] = [
+        Season::MarchEquinox,
+        Season::JuneSolstice,
+        Season::SeptemberEquinox,
+        Season::DecemberSolstice,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Season::MarchEquinox => "March equinox",
+            Season::JuneSolstice => "June solstice",
+            Season::SeptemberEquinox => "September equinox",
+            Season::DecemberSolstice => "December solstice",
+        }
+    }
+
+    // Function to find when this season starts in a year (valid for 1000 to 3000)
+    pub fn time(&self, year: i32) -> DateTime<Utc> {
+        let y = (year as f64 - 2000.0) / 1000.0;
+        let mean = match self {
+            Season::MarchEquinox => [2451623.80984, 365242.37404, 0.05169, -0.00411, -0.00057],
+            Season::JuneSolstice => [2451716.56767, 365241.62603, 0.00325, 0.00888, -0.00030],
+            Season::SeptemberEquinox => [2451810.21715, 365242.01767, -0.11575, 0.00337, 0.00078],
+            Season::DecemberSolstice => [2451900.05952, 365242.74049, -0.06223, -0.00823, 0.00032],
+        };
+        let jde0 = polynomial(&mean, y);
+
+        let t = (jde0 - 2451545.0) / 36525.0;
+        let w = (35999.373 * t - 2.47).to_radians();
+        let delta_lambda = 1.0 + 0.0334 * w.cos() + 0.0007 * (2.0 * w).cos();
+        let sum: f64 = SEASON_TERMS
+            .iter()
+            .map(|(a, b, c)| a * (b + c * t).to_radians().cos())
+            .sum();
+
+        dynamical_to_utc(jde0 + 0.00001 * sum / delta_lambda)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AstroEvent {
+    NewMoon,
+    FullMoon,
+    Season(Season),
+}
+
+impl AstroEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AstroEvent::NewMoon => "New moon",
+            AstroEvent::FullMoon => "Full moon",
+            AstroEvent::Season(season) => season.name(),
+        }
+    }
+
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            AstroEvent::NewMoon => "🌑",
+            AstroEvent::FullMoon => "🌕",
+            AstroEvent::Season(Season::MarchEquinox) => "🌱",
+            AstroEvent::Season(Season::JuneSolstice) => "☀",
+            AstroEvent::Season(Season::SeptemberEquinox) => "🍂",
+            AstroEvent::Season(Season::DecemberSolstice) => "❄",
+        }
+    }
+
+    // Single-column mark for monospace grids, where emoji would break the alignment
+    pub fn mark(&self) -> char {
+        match self {
+            AstroEvent::NewMoon => '●',
+            AstroEvent::FullMoon => '○',
+            AstroEvent::Season(_) => '✦',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoonPhase {
+    pub fraction: f64,     // 0 at new moon, 0.5 at full moon
+    pub illumination: f64, // lit share of the disc, 0.0 to 1.0
+}
+
+impl MoonPhase {
+    // Function to work out the phase at an instant from the surrounding new moons
+    pub fn at(time: DateTime<Utc>) -> Self {
+        let previous = previous_new_moon(time);
+        let next = moon_event(lunation_after(previous) + 1.0);
+        let fraction = ((time - previous).num_seconds() as f64
+            / (next - previous).num_seconds() as f64)
+            .clamp(0.0, 1.0);
+        MoonPhase {
+            fraction,
+            illumination: (1.0 - (2.0 * PI * fraction).cos()) / 2.0,
+        }
+    }
+
+    fn index(&self) -> usize {
+        ((self.fraction * 8.0).round() as usize) % 8
+    }
+
+    pub fn name(&self) -> &'static str {
+        [
+            "New moon",
+            "Waxing crescent",
+            "First quarter",
+            "Waxing gibbous",
+            "Full moon",
+            "Waning gibbous",
+            "Last quarter",
+            "Waning crescent",
+        ][self.index()]
+    }
+
+    pub fn glyph(&self) -> &'static str {
+        ["🌑", "🌒", "🌓", "🌔", "🌕", "🌖", "🌗", "🌘"][self.index()]
+    }
+}
+
+// Function to find the first new moon after an instant
+pub fn next_new_moon(after: DateTime<Utc>) -> DateTime<Utc> {
+    next_moon_event(after, 0.0)
+}
+
+// Function to find the first full moon after an instant
+pub fn next_full_moon(after: DateTime<Utc>) -> DateTime<Utc> {
+    next_moon_event(after, 0.5)
+}
+
+// Function to list this year's equinoxes and solstices
+pub fn seasons(year: i32) -> Vec<(Season, DateTime<Utc>)> {
+    Season::ALL
+        .iter()
+        .map(|season| (*season, season.time(year)))
+        .collect()
+}
+
+// Function to list the events falling in a local calendar month, in time order
+pub fn month_events(year: i32, month: u32) -> Vec<(DateTime<Local>, AstroEvent)> {
+    let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+        return Vec::new();
+    };
+    let start = local_midnight(first);
+    let end = first
+        .checked_add_months(chrono::Months::new(1))
+        .map(local_midnight)
+        .unwrap_or(start);
+
+    let mut events = Vec::new();
+    for (event, offset) in [(AstroEvent::NewMoon, 0.0), (AstroEvent::FullMoon, 0.5)] {
+        // Start a little early so an event right at midnight is not skipped
+        let mut time = next_moon_event(start - chrono::Duration::seconds(1), offset);
+        while time < end {
+            events.push((time, event));
+            time = next_moon_event(time + chrono::Duration::days(1), offset);
+        }
+    }
+    for (season, time) in seasons(year) {
+        if time >= start && time < end {
+            events.push((time, AstroEvent::Season(season)));
+        }
+    }
+
+    events.sort_by_key(|(time, _)| *time);
+    events
+        .into_iter()
+        .map(|(time, event)| (time.with_timezone(&Local), event))
+        .collect()
+}
+
+fn local_midnight(date: NaiveDate) -> DateTime<Utc> {
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0).expect("Invalid time"))
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).expect("Invalid time")))
+}
+
+fn next_moon_event(after: DateTime<Utc>, offset: f64) -> DateTime<Utc> {
+    let mut k = (lunation_after(after) - 1.0).floor() + offset;
+    loop {
+        let time = moon_event(k);
+        if time > after {
+            return time;
+        }
+        k += 1.0;
+    }
+}
+
+fn previous_new_moon(time: DateTime<Utc>) -> DateTime<Utc> {
+    let mut k = lunation_after(time).floor() + 1.0;
+    loop {
+        let new_moon = moon_event(k);
+        if new_moon <= time {
+            return new_moon;
+        }
+        k -= 1.0;
+    }
+}
+
+// Approximate lunation number k, counted from the new moon of 2000-01-06
+fn lunation_after(time: DateTime<Utc>) -> f64 {
+    let julian = time.timestamp() as f64 / 86400.0 + UNIX_EPOCH_JULIAN;
+    ((julian - 2451550.09766) / SYNODIC_MONTH).round()
+}
+
+// Function to compute a new moon (integer k) or full moon (k + 0.5)
+fn moon_event(k: f64) -> DateTime<Utc> {
+    let t = k / 1236.85;
+    let jde = polynomial(
+        &[2451550.09766, 0.0, 0.00015437, -0.000000150, 0.00000000073],
+        t,
+    ) + SYNODIC_MONTH * k;
+
+    let e = 1.0 - 0.002516 * t - 0.0000074 * t * t;
+    let m = (2.5534 + 29.10535670 * k - 0.0000014 * t * t).to_radians();
+    let m1 = (201.5643 + 385.81693528 * k + 0.0107582 * t * t).to_radians();
+    let f = (160.7108 + 390.67050284 * k - 0.0016118 * t * t).to_radians();
+    let omega = (124.7746 - 1.56375588 * k + 0.0020672 * t * t).to_radians();
+
+    let full = k.fract().abs() > 0.25;
+    let (a, b, c, d, g, h) = if full {
+        (-0.40614, 0.17302, 0.01614, 0.01043, 0.00734, -0.00515)
+    } else {
+        (-0.40720, 0.17241, 0.01608, 0.01039, 0.00739, -0.00514)
+    };
+    let correction = a * m1.sin()
+        + b * e * m.sin()
+        + c * (2.0 * m1).sin()
+        + d * (2.0 * f).sin()
+        + g * e * (m1 - m).sin()
+        + h * e * (m1 + m).sin()
+        + 0.00208 * e * e * (2.0 * m).sin()
+        - 0.00111 * (m1 - 2.0 * f).sin()
+        - 0.00057 * (m1 + 2.0 * f).sin()
+        + 0.00056 * e * (2.0 * m1 + m).sin()
+        - 0.00042 * (3.0 * m1).sin()
+        + 0.00042 * e * (m + 2.0 * f).sin()
+        + 0.00038 * e * (m - 2.0 * f).sin()
+        - 0.00024 * e * (2.0 * m1 - m).sin()
+        - 0.00017 * omega.sin();
+
+    dynamical_to_utc(jde + correction)
+}
+
+fn polynomial(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |sum, c| sum * x + c)
+}
+
+fn dynamical_to_utc(julian: f64) -> DateTime<Utc> {
+    let seconds = (julian - UNIX_EPOCH_JULIAN) * 86400.0 - DELTA_T;
+    Utc.timestamp_opt(seconds.round() as i64, 0)
+        .single()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Published times are given to the minute
+    fn assert_near(actual: DateTime<Utc>, expected: &str) {
+        let expected = DateTime::parse_from_rfc3339(expected).expect("Valid time");
+        let error = (actual - expected.with_timezone(&Utc)).num_seconds().abs();
+        assert!(error <= 180, "{} is {}s from {}", actual, error, expected);
+    }
+
+    #[test]
+    fn seasons_2024() {
+        let times: Vec<DateTime<Utc>> = seasons(2024).into_iter().map(|(_, time)| time).collect();
+        assert_near(times[0], "2024-03-20T03:06:00Z");
+        assert_near(times[1], "2024-06-20T20:51:00Z");
+        assert_near(times[2], "2024-09-22T12:44:00Z");
+        assert_near(times[3], "2024-12-21T09:20:00Z");
+    }
+
+    #[test]
+    fn new_moons() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        assert_near(next_new_moon(start), "2024-01-11T11:57:00Z");
+        let april = Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap();
+        assert_near(next_new_moon(april), "2024-04-08T18:21:00Z");
+    }
+
+    #[test]
+    fn full_moons() {
+        let april = Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap();
+        assert_near(next_full_moon(april), "2024-04-23T23:49:00Z");
+        let september = Utc.with_ymd_and_hms(2024, 9, 1, 0, 0, 0).unwrap();
+        assert_near(next_full_moon(september), "2024-09-18T02:34:00Z");
+    }
+
+    #[test]
+    fn phase_names() {
+        let full = Utc.with_ymd_and_hms(2024, 4, 23, 23, 49, 0).unwrap();
+        assert_eq!(MoonPhase::at(full).name(), "Full moon");
+        assert!(MoonPhase::at(full).illumination > 0.99);
+        let first_quarter = Utc.with_ymd_and_hms(2024, 4, 15, 19, 13, 0).unwrap();
+        assert_eq!(MoonPhase::at(first_quarter).name(), "First quarter");
+    }
+
+    #[test]
+    fn april_2024_events() {
+        // Both moons fall mid-month, so the local time zone does not matter
+        let events: Vec<AstroEvent> = month_events(2024, 4)
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect();
+        assert_eq!(events, vec![AstroEvent::NewMoon, AstroEvent::FullMoon]);
+    }
+}
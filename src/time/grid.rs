@@ -0,0 +1,245 @@
+// src/time/grid.rs
+// github.com/cvusmo/hyprclock
+
+// Month view as whole weeks of dates, free of GTK and of any formatting, so the tooltip text,
+// the popover and any later view lay out the same days.
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayCell {
+    pub date: NaiveDate,
+    pub in_month: bool, // false for the leading and trailing days of the neighbouring months
+    pub weekday: Weekday,
+    pub iso_week: u32,
+    pub events: usize, // moon phases, seasons and calendar events on this day
+    pub holiday: bool,
+    pub today: bool,
+}
+
+impl DayCell {
+    pub fn is_weekend(&self) -> bool {
+        matches!(self.weekday, Weekday::Sat | Weekday::Sun)
+    }
+
+    pub fn has_events(&self) -> bool {
+        self.events > 0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Week {
+    pub iso_week: u32, // week of the row's Monday, so a Sunday start shows the next week
+    pub days: Vec<DayCell>, // always seven
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthGrid {
+    pub year: i32,
+    pub month: u32,
+    pub week_start: Weekday,
+    pub weeks: Vec<Week>,
+}
+
+impl MonthGrid {
+    // Function to lay out a month from the week start on or before the 1st to the end of the
+    // week holding the last day; None for an invalid month
+    pub fn new(year: i32, month: u32, week_start: Weekday) -> Option<Self> {
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let offset =
+            (first.weekday().num_days_from_monday() + 7 - week_start.num_days_from_monday()) % 7;
+        let mut row_start = first - Duration::days(offset as i64);
+
+        let mut weeks = Vec::new();
+        while row_start <= first || (row_start.year(), row_start.month()) == (year, month) {
+            let days: Vec<DayCell> = (0..7)
+                .map(|i| {
+                    let date = row_start + Duration::days(i);
+                    DayCell {
+                        date,
+                        in_month: (date.year(), date.month()) == (year, month),
+                        weekday: date.weekday(),
+                        iso_week: date.iso_week().week(),
+                        events: 0,
+                        holiday: false,
+                        today: false,
+                    }
+                })
+                .collect();
+            let to_monday = (7 - row_start.weekday().num_days_from_monday()) % 7;
+            weeks.push(Week {
+                iso_week: (row_start + Duration::days(to_monday as i64))
+                    .iso_week()
+                    .week(),
+                days,
+            });
+            row_start += Duration::days(7);
+        }
+
+        Some(MonthGrid {
+            year,
+            month,
+            week_start,
+            weeks,
+        })
+    }
+
+    // Weekdays in column order
+    pub fn weekdays(&self) -> Vec<Weekday> {
+        self.weeks
+            .first()
+            .map(|week| week.days.iter().map(|day| day.weekday).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &DayCell> {
+        self.weeks.iter().flat_map(|week| week.days.iter())
+    }
+
+    pub fn cell(&self, date: NaiveDate) -> Option<&DayCell> {
+        self.cells().find(|cell| cell.date == date)
+    }
+
+    fn cell_mut(&mut self, date: NaiveDate) -> Option<&mut DayCell> {
+        self.weeks
+            .iter_mut()
+            .flat_map(|week| week.days.iter_mut())
+            .find(|cell| cell.date == date)
+    }
+
+    pub fn mark_today(&mut self, today: NaiveDate) {
+        if let Some(cell) = self.cell_mut(today) {
+            cell.today = true;
+        }
+    }
+
+    // Function to count one event per date; dates outside the grid are ignored
+    pub fn add_events(&mut self, dates: impl IntoIterator<Item = NaiveDate>) {
+        for date in dates {
+            if let Some(cell) = self.cell_mut(date) {
+                cell.events += 1;
+            }
+        }
+    }
+
+    pub fn mark_holidays(&mut self, dates: impl IntoIterator<Item = NaiveDate>) {
+        for date in dates {
+            if let Some(cell) = self.cell_mut(date) {
+                cell.holiday = true;
+            }
+        }
+    }
+
+    // Days of the month itself that have an event or a holiday, for calendar marks
+    pub fn marked_days(&self) -> Vec<u32> {
+        self.cells()
+            .filter(|cell| cell.in_month && (cell.has_events() || cell.holiday))
+            .map(|cell| cell.date.day())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEEK_STARTS: [Weekday; 7] = [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ];
+
+    // Leap and non-leap years, including both century rules
+    const YEARS: [i32; 4] = [2023, 2024, 2000, 2100];
+
+    fn last_day(year: i32, month: u32) -> NaiveDate {
+        let (next_year, next_month) = if month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, month + 1)
+        };
+        NaiveDate::from_ymd_opt(next_year, next_month, 1).unwrap() - Duration::days(1)
+    }
+
+    #[test]
+    fn every_layout() {
+        for year in YEARS {
+            for month in 1..=12 {
+                for week_start in WEEK_STARTS {
+                    check_layout(year, month, week_start);
+                }
+            }
+        }
+    }
+
+    fn check_layout(year: i32, month: u32, week_start: Weekday) {
+        let context = format!("{}-{:02} from {}", year, month, week_start);
+        let grid = MonthGrid::new(year, month, week_start).expect("Valid month");
+        let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+        let last = last_day(year, month);
+
+        assert!(
+            grid.cell(first).is_some_and(|cell| cell.in_month),
+            "{}",
+            context
+        );
+        assert!(
+            grid.cell(last).is_some_and(|cell| cell.in_month),
+            "{}",
+            context
+        );
+
+        let mut expected = grid.weeks[0].days[0].date;
+        for week in &grid.weeks {
+            assert_eq!(week.days.len(), 7, "{}", context);
+            assert_eq!(week.days[0].weekday, week_start, "{}", context);
+            // No row is left over with only days of the next month
+            assert!(week.days[0].date <= last, "{}", context);
+
+            let monday = week
+                .days
+                .iter()
+                .find(|day| day.weekday == Weekday::Mon)
+                .unwrap();
+            assert_eq!(week.iso_week, monday.date.iso_week().week(), "{}", context);
+
+            for day in &week.days {
+                // Consecutive dates, with nothing skipped between rows
+                assert_eq!(day.date, expected, "{}", context);
+                expected += Duration::days(1);
+                assert_eq!(day.weekday, day.date.weekday(), "{}", context);
+                assert_eq!(
+                    day.in_month,
+                    first <= day.date && day.date <= last,
+                    "{}",
+                    context
+                );
+                assert_eq!(day.iso_week, day.date.iso_week().week(), "{}", context);
+            }
+        }
+        // The first row starts on or before the 1st and the last ends on or after the last day
+        assert!(grid.weeks[0].days[0].date <= first, "{}", context);
+        assert!(expected > last, "{}", context);
+
+        let days_in_month = grid.cells().filter(|cell| cell.in_month).count() as u32;
+        assert_eq!(days_in_month, last.day(), "{}", context);
+    }
+
+    #[test]
+    fn february_length() {
+        for (year, days) in [(2023, 28), (2024, 29), (2000, 29), (2100, 28)] {
+            let grid = MonthGrid::new(year, 2, Weekday::Mon).unwrap();
+            assert_eq!(grid.cells().filter(|cell| cell.in_month).count(), days);
+        }
+    }
+
+    #[test]
+    fn invalid_month() {
+        assert_eq!(MonthGrid::new(2024, 13, Weekday::Mon), None);
+        assert_eq!(MonthGrid::new(2024, 0, Weekday::Mon), None);
+    }
+}
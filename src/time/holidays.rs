@@ -0,0 +1,407 @@
+// src/time/holidays.rs
+// github.com/cvusmo/hyprclock
+
+// Rule-based public holidays, evaluated offline. Substitute days for holidays falling on a
+// weekend are not modelled.
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+// Countries with a built-in rule set
+pub const COUNTRIES: [&str; 5] = ["DE", "FR", "GB", "NL", "US"];
+
+// One rule, as written in a user rule file; exactly one of date, month + weekday + nth or
+// easter must be given
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HolidayRule {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>, // MM-DD, every year
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub month: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weekday: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nth: Option<i32>, // 1 for the first, -1 for the last weekday of the month
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub easter: Option<i64>, // days after Easter Sunday, negative for before
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regions: Vec<String>, // empty for the whole country
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleFile {
+    #[serde(default)]
+    holiday: Vec<HolidayRule>,
+}
+
+impl HolidayRule {
+    fn fixed(name: &str, month: u32, day: u32) -> Self {
+        HolidayRule {
+            name: name.to_string(),
+            date: Some(format!("{:02}-{:02}", month, day)),
+            ..Default::default()
+        }
+    }
+
+    fn nth(name: &str, month: u32, weekday: &str, nth: i32) -> Self {
+        HolidayRule {
+            name: name.to_string(),
+            month: Some(month),
+            weekday: Some(weekday.to_string()),
+            nth: Some(nth),
+            ..Default::default()
+        }
+    }
+
+    fn easter(name: &str, offset: i64) -> Self {
+        HolidayRule {
+            name: name.to_string(),
+            easter: Some(offset),
+            ..Default::default()
+        }
+    }
+
+    fn only(mut self, regions: &[&str]) -> Self {
+        self.regions = regions.iter().map(|region| region.to_string()).collect();
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let kinds = [
+            self.date.is_some(),
+            self.month.is_some() || self.weekday.is_some() || self.nth.is_some(),
+            self.easter.is_some(),
+        ];
+        if kinds.iter().filter(|kind| **kind).count() != 1 {
+            return Err(format!(
+                "Holiday '{}' needs exactly one of date, month/weekday/nth or easter",
+                self.name
+            ));
+        }
+        // A leap day is the only date that can be missing from a year
+        if self.date.is_some() && self.date_in(2024).is_none() {
+            return Err(format!(
+                "Holiday '{}' has an invalid date, expected MM-DD",
+                self.name
+            ));
+        }
+        let weekday_rule = self.month.is_some_and(|month| (1..=12).contains(&month))
+            && self
+                .weekday
+                .as_deref()
+                .is_some_and(|weekday| weekday.trim().parse::<Weekday>().is_ok())
+            && self
+                .nth
+                .is_some_and(|nth| nth != 0 && (-5..=5).contains(&nth));
+        if self.date.is_none() && self.easter.is_none() && !weekday_rule {
+            return Err(format!(
+                "Holiday '{}' needs month, weekday and a non-zero nth between -5 and 5",
+                self.name
+            ));
+        }
+        Ok(())
+    }
+
+    // Function to find the date this rule falls on in a year
+    pub fn date_in(&self, year: i32) -> Option<NaiveDate> {
+        if let Some(date) = &self.date {
+            let (month, day) = date.split_once('-')?;
+            return NaiveDate::from_ymd_opt(year, month.parse().ok()?, day.parse().ok()?);
+        }
+        if let Some(offset) = self.easter {
+            return easter_sunday(year).map(|easter| easter + Duration::days(offset));
+        }
+        let weekday = self.weekday.as_deref()?.trim().parse::<Weekday>().ok()?;
+        let nth = self.nth?;
+        if nth == 0 || !(-5..=5).contains(&nth) {
+            return None;
+        }
+        let month = self.month?;
+        if nth > 0 {
+            NaiveDate::from_weekday_of_month_opt(year, month, weekday, nth as u8)
+        } else {
+            // Count back from the last day of the month
+            let last = NaiveDate::from_ymd_opt(year, month, 1)?
+                .checked_add_months(chrono::Months::new(1))?
+                .pred_opt()?;
+            let back =
+                (last.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+            let date = last - Duration::days(back as i64 + 7 * (-nth as i64 - 1));
+            (date.month() == month).then_some(date)
+        }
+    }
+
+    fn applies_to(&self, region: Option<&str>) -> bool {
+        self.regions.is_empty()
+            || region
+                .is_some_and(|region| self.regions.iter().any(|r| r.eq_ignore_ascii_case(region)))
+    }
+}
+
+// Function to compute Easter Sunday with the anonymous Gregorian algorithm
+pub fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+// Function to return the built-in rules of a country
+pub fn builtin_rules(country: &str) -> Option<Vec<HolidayRule>> {
+    use HolidayRule as R;
+    let rules = match country.to_ascii_uppercase().as_str() {
+        "DE" => vec![
+            R::fixed("Neujahr", 1, 1),
+            R::fixed("Heilige Drei Könige", 1, 6).only(&["BW", "BY", "ST"]),
+            R::fixed("Internationaler Frauentag", 3, 8).only(&["BE", "MV"]),
+            R::easter("Karfreitag", -2),
+            R::easter("Ostermontag", 1),
+            R::fixed("Tag der Arbeit", 5, 1),
+            R::easter("Christi Himmelfahrt", 39),
+            R::easter("Pfingstmontag", 50),
+            R::easter("Fronleichnam", 60).only(&["BW", "BY", "HE", "NW", "RP", "SL"]),
+            R::fixed("Mariä Himmelfahrt", 8, 15).only(&["SL"]),
+            R::fixed("Weltkindertag", 9, 20).only(&["TH"]),
+            R::fixed("Tag der Deutschen Einheit", 10, 3),
+            R::fixed("Reformationstag", 10, 31)
+                .only(&["BB", "HB", "HH", "MV", "NI", "SH", "SN", "ST", "TH"]),
+            R::fixed("Allerheiligen", 11, 1).only(&["BW", "BY", "NW", "RP", "SL"]),
+            R::fixed("1. Weihnachtstag", 12, 25),
+            R::fixed("2. Weihnachtstag", 12, 26),
+        ],
+        "FR" => vec![
+            R::fixed("Jour de l'an", 1, 1),
+            R::easter("Vendredi saint", -2).only(&["57", "67", "68"]),
+            R::easter("Lundi de Pâques", 1),
+            R::fixed("Fête du Travail", 5, 1),
+            R::fixed("Victoire 1945", 5, 8),
+            R::easter("Ascension", 39),
+            R::easter("Lundi de Pentecôte", 50),
+            R::fixed("Fête nationale", 7, 14),
+            R::fixed("Assomption", 8, 15),
+            R::fixed("Toussaint", 11, 1),
+            R::fixed("Armistice 1918", 11, 11),
+            R::fixed("Noël", 12, 25),
+            R::fixed("Saint-Étienne", 12, 26).only(&["57", "67", "68"]),
+        ],
+        "GB" => vec![
+            R::fixed("New Year's Day", 1, 1),
+            R::fixed("2nd January", 1, 2).only(&["SCT"]),
+            R::fixed("St Patrick's Day", 3, 17).only(&["NIR"]),
+            R::easter("Good Friday", -2),
+            R::easter("Easter Monday", 1).only(&["ENG", "WLS", "NIR"]),
+            R::nth("Early May bank holiday", 5, "mon", 1),
+            R::nth("Spring bank holiday", 5, "mon", -1),
+            R::fixed("Battle of the Boyne", 7, 12).only(&["NIR"]),
+            R::nth("Summer bank holiday", 8, "mon", 1).only(&["SCT"]),
+            R::nth("Summer bank holiday", 8, "mon", -1).only(&["ENG", "WLS", "NIR"]),
+            R::fixed("St Andrew's Day", 11, 30).only(&["SCT"]),
+            R::fixed("Christmas Day", 12, 25),
+            R::fixed("Boxing Day", 12, 26),
+        ],
+        "NL" => vec![
+            R::fixed("Nieuwjaarsdag", 1, 1),
+            R::easter("Eerste Paasdag", 0),
+            R::easter("Tweede Paasdag", 1),
+            R::fixed("Koningsdag", 4, 27),
+            R::fixed("Bevrijdingsdag", 5, 5),
+            R::easter("Hemelvaartsdag", 39),
+            R::easter("Eerste Pinksterdag", 49),
+            R::easter("Tweede Pinksterdag", 50),
+            R::fixed("Eerste Kerstdag", 12, 25),
+            R::fixed("Tweede Kerstdag", 12, 26),
+        ],
+        "US" => vec![
+            R::fixed("New Year's Day", 1, 1),
+            R::nth("Martin Luther King Jr. Day", 1, "mon", 3),
+            R::nth("Presidents' Day", 2, "mon", 3),
+            R::nth("Memorial Day", 5, "mon", -1),
+            R::fixed("Juneteenth", 6, 19),
+            R::fixed("Independence Day", 7, 4),
+            R::nth("Labor Day", 9, "mon", 1),
+            R::nth("Columbus Day", 10, "mon", 2),
+            R::fixed("Veterans Day", 11, 11),
+            R::nth("Thanksgiving", 11, "thu", 4),
+            R::fixed("Christmas Day", 12, 25),
+        ],
+        _ => return None,
+    };
+    Some(rules)
+}
+
+// Function to list the region codes a country's built-in rules use, e.g. "BY" for DE
+pub fn builtin_regions(country: &str) -> Vec<String> {
+    let mut regions: Vec<String> = builtin_rules(country)
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|rule| rule.regions)
+        .collect();
+    regions.sort();
+    regions.dedup();
+    regions
+}
+
+// Function to read `[[holiday]]` rules from a user file
+pub fn load_rule_file(path: &Path) -> Result<Vec<HolidayRule>, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let file: RuleFile = toml::from_str(&contents)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+    for rule in &file.holiday {
+        rule.validate()?;
+    }
+    Ok(file.holiday)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+// The rules selected for a list of countries and regions, plus any user rules
+#[derive(Debug, Clone, Default)]
+pub struct HolidayCalendar {
+    rules: Vec<(Option<String>, HolidayRule)>, // region the rule was selected for
+}
+
+impl HolidayCalendar {
+    // Function to select rules for entries such as "US", "DE-BY" or "GB-SCT"
+    pub fn new(countries: &[String], custom: Vec<HolidayRule>) -> Result<Self, String> {
+        let mut rules = Vec::new();
+        for entry in countries {
+            let (country, region) = match entry.split_once('-') {
+                Some((country, region)) => (country, Some(region.to_string())),
+                None => (entry.as_str(), None),
+            };
+            let country_rules =
+                builtin_rules(country).ok_or_else(|| format!("Unknown country: {}", country))?;
+            rules.extend(
+                country_rules
+                    .into_iter()
+                    .filter(|rule| rule.applies_to(region.as_deref()))
+                    .map(|rule| (region.clone(), rule)),
+            );
+        }
+        rules.extend(custom.into_iter().map(|rule| (None, rule)));
+        Ok(HolidayCalendar { rules })
+    }
+
+    // Function to list a year's holidays in date order, each name once per day
+    pub fn in_year(&self, year: i32) -> Vec<Holiday> {
+        let mut holidays: Vec<Holiday> = self
+            .rules
+            .iter()
+            .filter_map(|(_, rule)| {
+                rule.date_in(year).map(|date| Holiday {
+                    date,
+                    name: rule.name.clone(),
+                })
+            })
+            .collect();
+        holidays.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.name.cmp(&b.name)));
+        holidays.dedup();
+        holidays
+    }
+
+    pub fn in_month(&self, year: i32, month: u32) -> Vec<Holiday> {
+        self.in_year(year)
+            .into_iter()
+            .filter(|holiday| holiday.date.month() == month)
+            .collect()
+    }
+
+    pub fn on(&self, date: NaiveDate) -> Vec<Holiday> {
+        self.in_year(date.year())
+            .into_iter()
+            .filter(|holiday| holiday.date == date)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn holiday_date(countries: &[&str], year: i32, name: &str) -> Option<NaiveDate> {
+        let countries: Vec<String> = countries.iter().map(|c| c.to_string()).collect();
+        HolidayCalendar::new(&countries, Vec::new())
+            .unwrap()
+            .in_year(year)
+            .into_iter()
+            .find(|holiday| holiday.name == name)
+            .map(|holiday| holiday.date)
+    }
+
+    #[test]
+    fn easter() {
+        assert_eq!(easter_sunday(2024), Some(date(2024, 3, 31)));
+        assert_eq!(easter_sunday(2025), Some(date(2025, 4, 20)));
+        assert_eq!(easter_sunday(2000), Some(date(2000, 4, 23)));
+        // Latest possible date
+        assert_eq!(easter_sunday(2038), Some(date(2038, 4, 25)));
+    }
+
+    #[test]
+    fn nth_weekday() {
+        assert_eq!(
+            holiday_date(&["US"], 2024, "Thanksgiving"),
+            Some(date(2024, 11, 28))
+        );
+        assert_eq!(
+            holiday_date(&["US"], 2024, "Martin Luther King Jr. Day"),
+            Some(date(2024, 1, 15))
+        );
+        assert_eq!(
+            holiday_date(&["GB"], 2024, "Early May bank holiday"),
+            Some(date(2024, 5, 6))
+        );
+    }
+
+    #[test]
+    fn last_weekday() {
+        assert_eq!(
+            holiday_date(&["GB"], 2024, "Spring bank holiday"),
+            Some(date(2024, 5, 27))
+        );
+        assert_eq!(
+            holiday_date(&["US"], 2024, "Memorial Day"),
+            Some(date(2024, 5, 27))
+        );
+        // May 2021 ended on a Monday
+        assert_eq!(
+            holiday_date(&["US"], 2021, "Memorial Day"),
+            Some(date(2021, 5, 31))
+        );
+    }
+
+    #[test]
+    fn regional_rules() {
+        assert_eq!(
+            holiday_date(&["DE-BY"], 2024, "Fronleichnam"),
+            Some(date(2024, 5, 30))
+        );
+        assert_eq!(holiday_date(&["DE"], 2024, "Fronleichnam"), None);
+        assert_eq!(holiday_date(&["DE-BE"], 2024, "Fronleichnam"), None);
+        assert!(builtin_regions("DE").contains(&"BY".to_string()));
+        assert!(builtin_regions("US").is_empty());
+    }
+}
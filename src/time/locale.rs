@@ -0,0 +1,110 @@
+// src/time/locale.rs
+// github.com/cvusmo/hyprclock
+
+use chrono::Weekday;
+use glib::DateTime;
+use std::ffi::{CStr, CString};
+
+// glibc LC_TIME items that the libc crate does not export
+const NL_TIME_WEEK_1STDAY: libc::nl_item = 0x20066;
+const NL_TIME_FIRST_WEEKDAY: libc::nl_item = 0x20068;
+
+// Function to switch LC_TIME, either to the configured locale or to the environment
+pub fn apply_locale(locale: Option<&str>) -> Result<(), String> {
+    let name = CString::new(locale.unwrap_or(""))
+        .map_err(|_| "Locale name must not contain NUL bytes".to_string())?;
+
+    // setlocale changes the whole process; it only runs on the main thread, at startup and on
+    // config reloads, and the clock formats its text on that thread too
+    let result = unsafe { libc::setlocale(libc::LC_TIME, name.as_ptr()) };
+    if result.is_null() {
+        Err(format!(
+            "Locale '{}' is not available on this system",
+            locale.unwrap_or("")
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+// Function to check a locale without touching the process locale, so it is safe to call
+// while other threads format times
+pub fn locale_available(locale: &str) -> bool {
+    let Ok(name) = CString::new(locale) else {
+        return false;
+    };
+    let handle =
+        unsafe { libc::newlocale(libc::LC_TIME_MASK, name.as_ptr(), std::ptr::null_mut()) };
+    if handle.is_null() {
+        return false;
+    }
+    unsafe { libc::freelocale(handle) };
+    true
+}
+
+// Function to tell whether the locale's time format uses a 12-hour clock
+pub fn uses_12_hour() -> bool {
+    let format = unsafe { CStr::from_ptr(libc::nl_langinfo(libc::T_FMT)) };
+    let format = format.to_string_lossy();
+    format.contains("%I") || format.contains("%l") || format.contains("%r")
+}
+
+// Function to read the locale's first day of the week
+pub fn first_weekday() -> Weekday {
+    // The week origin is a date packed into the pointer value, 19971130 being a Sunday
+    let week_origin = unsafe { libc::nl_langinfo(NL_TIME_WEEK_1STDAY) } as usize as u32;
+    let first_weekday = unsafe { *libc::nl_langinfo(NL_TIME_FIRST_WEEKDAY) } as u32;
+    let origin_from_sunday = match week_origin {
+        19971201 => 1,
+        _ => 0,
+    };
+
+    match (origin_from_sunday + first_weekday.max(1) - 1) % 7 {
+        0 => Weekday::Sun,
+        1 => Weekday::Mon,
+        2 => Weekday::Tue,
+        3 => Weekday::Wed,
+        4 => Weekday::Thu,
+        5 => Weekday::Fri,
+        _ => Weekday::Sat,
+    }
+}
+
+// Function to build two-column weekday headers in the current locale
+pub fn weekday_headers(first: Weekday) -> Vec<String> {
+    // 2024-01-01 was a Monday
+    (0..7)
+        .map(|i| {
+            let day = 1 + (first.num_days_from_monday() + i) % 7;
+            let name = DateTime::from_utc(2024, 1, day as i32, 12, 0, 0.0)
+                .and_then(|date| date.format("%a"))
+                .map(|name| name.to_string())
+                .unwrap_or_default();
+            abbreviate(&name)
+        })
+        .collect()
+}
+
+// Trim a weekday name to two terminal columns, counting wide characters twice
+fn abbreviate(name: &str) -> String {
+    let mut result = String::new();
+    let mut width = 0;
+    for c in name.chars() {
+        let char_width = if is_wide(c) { 2 } else { 1 };
+        if width + char_width > 2 {
+            break;
+        }
+        result.push(c);
+        width += char_width;
+    }
+    while width < 2 {
+        result.push(' ');
+        width += 1;
+    }
+    result
+}
+
+fn is_wide(c: char) -> bool {
+    matches!(c as u32,
+        0x1100..=0x115F | 0x2E80..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFF00..=0xFF60)
+}
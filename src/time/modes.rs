@@ -0,0 +1,253 @@
+// src/time/modes.rs
+// github.com/cvusmo/hyprclock
+
+use crate::time::placeholders::Placeholders;
+use crate::time::tick::TickUnit;
+use glib::DateTime;
+
+// Display modes accepted by the `display` key
+pub const DISPLAY_MODES: [&str; 7] = ["digits", "words", "binary", "bcd", "epoch", "beat", "hex"];
+
+// Common interface for every way of rendering the current time
+pub trait TimeFormatter {
+    fn format(&self, time: &DateTime) -> String;
+
+    // Smallest unit after which the rendered text can change
+    fn tick_unit(&self) -> TickUnit;
+}
+
+// Function to pick the formatter for a display mode
+pub fn formatter_for(
+    display: &str,
+    pattern: &str,
+    placeholders: &Placeholders,
+) -> Box<dyn TimeFormatter> {
+    match display {
+        "words" => Box::new(WordClock),
+        "binary" => Box::new(BinaryClock),
+        "bcd" => Box::new(BcdClock),
+        "epoch" => Box::new(EpochClock),
+        "beat" => Box::new(BeatClock),
+        "hex" => Box::new(HexClock),
+        _ => Box::new(DigitalClock {
+            pattern: pattern.to_string(),
+            placeholders: placeholders.clone(),
+        }),
+    }
+}
+
+// strftime digits with `{name}` placeholders, the default
+pub struct DigitalClock {
+    pub pattern: String,
+    pub placeholders: Placeholders,
+}
+
+impl TimeFormatter for DigitalClock {
+    fn format(&self, time: &DateTime) -> String {
+        time.format(&self.placeholders.expand_for_strftime(&self.pattern, time))
+            .expect("Format failed")
+            .to_string()
+    }
+
+    fn tick_unit(&self) -> TickUnit {
+        TickUnit::from_format(&self.pattern)
+    }
+}
+
+// Fuzzy clock rounded to five minutes, e.g. "quarter past ten"
+pub struct WordClock;
+
+impl TimeFormatter for WordClock {
+    fn format(&self, time: &DateTime) -> String {
+        word_time(time.hour() as u32, time.minute() as u32)
+    }
+
+    fn tick_unit(&self) -> TickUnit {
+        TickUnit::Minute
+    }
+}
+
+// Hours, minutes and seconds as plain binary numbers
+pub struct BinaryClock;
+
+impl TimeFormatter for BinaryClock {
+    fn format(&self, time: &DateTime) -> String {
+        binary_time(
+            time.hour() as u32,
+            time.minute() as u32,
+            time.second() as u32,
+        )
+    }
+
+    fn tick_unit(&self) -> TickUnit {
+        TickUnit::Second
+    }
+}
+
+// Every decimal digit as its own four-bit group
+pub struct BcdClock;
+
+impl TimeFormatter for BcdClock {
+    fn format(&self, time: &DateTime) -> String {
+        bcd_time(
+            time.hour() as u32,
+            time.minute() as u32,
+            time.second() as u32,
+        )
+    }
+
+    fn tick_unit(&self) -> TickUnit {
+        TickUnit::Second
+    }
+}
+
+// Seconds since 1970-01-01 UTC
+pub struct EpochClock;
+
+impl TimeFormatter for EpochClock {
+    fn format(&self, time: &DateTime) -> String {
+        time.to_unix().to_string()
+    }
+
+    fn tick_unit(&self) -> TickUnit {
+        TickUnit::Second
+    }
+}
+
+// Swatch Internet Time, 1000 beats per day on Biel Mean Time
+pub struct BeatClock;
+
+impl TimeFormatter for BeatClock {
+    fn format(&self, time: &DateTime) -> String {
+        swatch_beat(time.to_unix())
+    }
+
+    fn tick_unit(&self) -> TickUnit {
+        TickUnit::Second
+    }
+}
+
+// Day split into 65536 hexadecimal seconds, written hour_maxime_second
+pub struct HexClock;
+
+impl TimeFormatter for HexClock {
+    fn format(&self, time: &DateTime) -> String {
+        hex_time(time.hour() as u32 * 3600 + time.minute() as u32 * 60 + time.second() as u32)
+    }
+
+    fn tick_unit(&self) -> TickUnit {
+        TickUnit::Second
+    }
+}
+
+const HOUR_WORDS: [&str; 12] = [
+    "twelve", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven",
+];
+
+pub fn word_time(hour: u32, minute: u32) -> String {
+    // Round to the nearest five minutes, carrying into the next hour
+    let rounded = (minute + 2) / 5 * 5;
+    let (hour, rounded) = if rounded == 60 {
+        ((hour + 1) % 24, 0)
+    } else {
+        (hour, rounded)
+    };
+
+    let named_hour = |hour: u32| match hour {
+        0 => "midnight".to_string(),
+        12 => "noon".to_string(),
+        _ => HOUR_WORDS[(hour % 12) as usize].to_string(),
+    };
+
+    match rounded {
+        0 if hour == 0 || hour == 12 => named_hour(hour),
+        0 => format!("{} o'clock", named_hour(hour)),
+        5 => format!("five past {}", named_hour(hour)),
+        10 => format!("ten past {}", named_hour(hour)),
+        15 => format!("quarter past {}", named_hour(hour)),
+        20 => format!("twenty past {}", named_hour(hour)),
+        25 => format!("twenty-five past {}", named_hour(hour)),
+        30 => format!("half past {}", named_hour(hour)),
+        35 => format!("twenty-five to {}", named_hour((hour + 1) % 24)),
+        40 => format!("twenty to {}", named_hour((hour + 1) % 24)),
+        45 => format!("quarter to {}", named_hour((hour + 1) % 24)),
+        50 => format!("ten to {}", named_hour((hour + 1) % 24)),
+        _ => format!("five to {}", named_hour((hour + 1) % 24)),
+    }
+}
+
+pub fn binary_time(hour: u32, minute: u32, second: u32) -> String {
+    format!("{:05b}:{:06b}:{:06b}", hour, minute, second)
+}
+
+pub fn bcd_time(hour: u32, minute: u32, second: u32) -> String {
+    [hour, minute, second]
+        .iter()
+        .map(|value| format!("{:04b} {:04b}", value / 10, value % 10))
+        .collect::<Vec<_>>()
+        .join(" : ")
+}
+
+pub fn swatch_beat(unix_seconds: i64) -> String {
+    // Biel Mean Time is UTC+1 all year round
+    let seconds_into_day = (unix_seconds + 3600).rem_euclid(86400);
+    format!("@{:03}", seconds_into_day * 10 / 864)
+}
+
+pub fn hex_time(seconds_into_day: u32) -> String {
+    let value = (seconds_into_day as u64 * 65536 / 86400) as u32;
+    format!(
+        "{:X}_{:02X}_{:X}",
+        value >> 12,
+        (value >> 4) & 0xFF,
+        value & 0xF
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(hour: i32) -> DateTime {
+        DateTime::from_utc(1970, 1, 1, hour, 0, 0.0).expect("Valid date")
+    }
+
+    #[test]
+    fn words_at_midnight_and_noon() {
+        assert_eq!(word_time(0, 0), "midnight");
+        assert_eq!(word_time(12, 0), "noon");
+    }
+
+    #[test]
+    fn binary_at_midnight_and_noon() {
+        assert_eq!(binary_time(0, 0, 0), "00000:000000:000000");
+        assert_eq!(binary_time(12, 0, 0), "01100:000000:000000");
+    }
+
+    #[test]
+    fn bcd_at_midnight_and_noon() {
+        assert_eq!(bcd_time(0, 0, 0), "0000 0000 : 0000 0000 : 0000 0000");
+        assert_eq!(bcd_time(12, 0, 0), "0001 0010 : 0000 0000 : 0000 0000");
+    }
+
+    #[test]
+    fn epoch_at_midnight_and_noon() {
+        assert_eq!(EpochClock.format(&utc(0)), "0");
+        assert_eq!(EpochClock.format(&utc(12)), "43200");
+    }
+
+    #[test]
+    fn beat_at_midnight_and_noon() {
+        // Midnight UTC is already 01:00 Biel Mean Time
+        assert_eq!(BeatClock.format(&utc(0)), "@041");
+        assert_eq!(BeatClock.format(&utc(12)), "@541");
+    }
+
+    #[test]
+    fn hex_at_midnight_and_noon() {
+        assert_eq!(hex_time(0), "0_00_0");
+        assert_eq!(hex_time(12 * 3600), "8_00_0");
+        assert_eq!(HexClock.format(&utc(12)), "8_00_0");
+    }
+}
@@ -0,0 +1,244 @@
+// src/time/placeholders.rs
+// github.com/cvusmo/hyprclock
+
+use crate::time::astronomy::MoonPhase;
+use crate::time::sun::SunTimes;
+use chrono::{Datelike, NaiveDate, TimeZone, Utc};
+use glib::DateTime;
+
+// `{name}` placeholders accepted in label formats, expanded before strftime
+pub const PLACEHOLDERS: [&str; 14] = [
+    "week",
+    "week_year",
+    "moon",
+    "moon_phase",
+    "illumination",
+    "sunrise",
+    "sunset",
+    "noon",
+    "dawn",
+    "dusk",
+    "nautical_dawn",
+    "nautical_dusk",
+    "astronomical_dawn",
+    "astronomical_dusk",
+];
+
+// Placeholders that only need the time itself
+const MOON_PLACEHOLDERS: [&str; 3] = ["moon", "moon_phase", "illumination"];
+const WEEK_PLACEHOLDERS: [&str; 2] = ["week", "week_year"];
+
+// Shown when an event does not happen today, e.g. no sunset in polar summer
+const MISSING: &str = "--:--";
+
+// Everything placeholders need beyond the time itself
+#[derive(Debug, Clone, Default)]
+pub struct Placeholders {
+    pub coordinates: Option<(f64, f64)>, // latitude, longitude
+}
+
+impl Placeholders {
+    // Function to replace known `{name}` placeholders and `{{`/`}}` escapes, leaving anything
+    // else untouched
+    pub fn expand(&self, pattern: &str, time: &DateTime) -> String {
+        self.expand_with(pattern, time, false)
+    }
+
+    // Same, with `%` in the values escaped so the result can still go through strftime
+    pub fn expand_for_strftime(&self, pattern: &str, time: &DateTime) -> String {
+        self.expand_with(pattern, time, true)
+    }
+
+    fn expand_with(&self, pattern: &str, time: &DateTime, escape: bool) -> String {
+        if !pattern.contains(['{', '}']) {
+            return pattern.to_string();
+        }
+
+        let sun = self.coordinates.and_then(|(latitude, longitude)| {
+            NaiveDate::from_ymd_opt(time.year(), time.month() as u32, time.day_of_month() as u32)
+                .map(|date| SunTimes::new(date, latitude, longitude))
+        });
+
+        let mut expanded = String::with_capacity(pattern.len());
+        for segment in segments(pattern) {
+            match segment {
+                Segment::Text(text) => expanded.push_str(&text),
+                Segment::Placeholder(name) if PLACEHOLDERS.contains(&name) => {
+                    let value = if MOON_PLACEHOLDERS.contains(&name) {
+                        moon_value(name, time)
+                    } else if WEEK_PLACEHOLDERS.contains(&name) {
+                        week_value(name, time)
+                    } else {
+                        sun_value(name, sun.as_ref(), time)
+                    };
+                    if escape {
+                        expanded.push_str(&value.replace('%', "%%"));
+                    } else {
+                        expanded.push_str(&value);
+                    }
+                }
+                Segment::Placeholder(name) => {
+                    expanded.push('{');
+                    expanded.push_str(name);
+                    expanded.push('}');
+                }
+            }
+        }
+        expanded
+    }
+}
+
+enum Segment<'a> {
+    Text(String),
+    Placeholder(&'a str),
+}
+
+// Function to split a pattern into literal text and `{name}` placeholders; `{{` and `}}` stand
+// for literal braces, as do braces that do not enclose a name
+fn segments(pattern: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut rest = pattern;
+    while let Some(index) = rest.find(['{', '}']) {
+        text.push_str(&rest[..index]);
+        let brace = &rest[index..index + 1];
+        let after = &rest[index + 1..];
+        if after.starts_with(brace) {
+            text.push_str(brace);
+            rest = &after[1..];
+            continue;
+        }
+        match after.find('}') {
+            Some(close) if brace == "{" => {
+                segments.push(Segment::Text(std::mem::take(&mut text)));
+                segments.push(Segment::Placeholder(&after[..close]));
+                rest = &after[close + 1..];
+            }
+            _ => {
+                text.push_str(brace);
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    segments.push(Segment::Text(text));
+    segments
+}
+
+// ISO 8601 week; the last days of December can fall in week 1 of the next year, and the
+// first days of January in week 52 or 53 of the last one, hence `{week_year}`
+fn week_value(name: &str, time: &DateTime) -> String {
+    let Some(date) =
+        NaiveDate::from_ymd_opt(time.year(), time.month() as u32, time.day_of_month() as u32)
+    else {
+        return String::new();
+    };
+    let week = date.iso_week();
+    match name {
+        "week" => format!("{:02}", week.week()),
+        _ => week.year().to_string(),
+    }
+}
+
+fn moon_value(name: &str, time: &DateTime) -> String {
+    let instant = Utc
+        .timestamp_opt(time.to_unix(), 0)
+        .single()
+        .unwrap_or_default();
+    let phase = MoonPhase::at(instant);
+    match name {
+        "moon" => phase.glyph().to_string(),
+        "moon_phase" => phase.name().to_string(),
+        _ => format!("{:.0}%", phase.illumination * 100.0),
+    }
+}
+
+fn sun_value(name: &str, sun: Option<&SunTimes>, time: &DateTime) -> String {
+    let Some(sun) = sun else {
+        return MISSING.to_string();
+    };
+    let event = match name {
+        "sunrise" => sun.daylight.start(),
+        "sunset" => sun.daylight.end(),
+        "noon" => Some(sun.noon),
+        "dawn" => sun.civil.start(),
+        "dusk" => sun.civil.end(),
+        "nautical_dawn" => sun.nautical.start(),
+        "nautical_dusk" => sun.nautical.end(),
+        "astronomical_dawn" => sun.astronomical.start(),
+        "astronomical_dusk" => sun.astronomical.end(),
+        _ => None,
+    };
+    event
+        .map(|event| format_event(event, time))
+        .unwrap_or_else(|| MISSING.to_string())
+}
+
+// Function to show an event in the same time zone as the clock it belongs to
+pub fn format_event(event: chrono::DateTime<Utc>, time: &DateTime) -> String {
+    DateTime::from_unix_utc(event.timestamp())
+        .and_then(|event| event.to_timezone(&time.timezone()))
+        .ok()
+        .and_then(|event| event.format("%H:%M").ok())
+        .map(|text| text.to_string())
+        .unwrap_or_else(|| MISSING.to_string())
+}
+
+// Function to list the placeholder names a pattern uses
+pub fn placeholder_names(pattern: &str) -> Vec<&str> {
+    segments(pattern)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Placeholder(name) => Some(name),
+            Segment::Text(_) => None,
+        })
+        .collect()
+}
+
+// Function to reject unknown placeholders and sun placeholders without a location
+pub fn validate_placeholders(pattern: &str, placeholders: &Placeholders) -> Result<(), String> {
+    for name in placeholder_names(pattern) {
+        if !PLACEHOLDERS.contains(&name) {
+            return Err(format!(
+                "Unknown placeholder '{{{}}}' in \"{}\", write {{{{ for a literal brace",
+                name, pattern
+            ));
+        }
+        if placeholders.coordinates.is_none()
+            && !MOON_PLACEHOLDERS.contains(&name)
+            && !WEEK_PLACEHOLDERS.contains(&name)
+        {
+            return Err(format!(
+                "Placeholder '{{{}}}' needs latitude and longitude",
+                name
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noon() -> DateTime {
+        DateTime::from_utc(2024, 1, 1, 12, 0, 0.0).expect("Valid date")
+    }
+
+    #[test]
+    fn doubled_braces_are_literal() {
+        let placeholders = Placeholders::default();
+        assert_eq!(placeholders.expand("{{week}}", &noon()), "{week}");
+        assert_eq!(placeholders.expand("{{{week}}}", &noon()), "{01}");
+        assert_eq!(placeholder_names("{{sunrise}} {week}"), vec!["week"]);
+        assert!(validate_placeholders("%H:%M {{not a placeholder}}", &placeholders).is_ok());
+    }
+
+    #[test]
+    fn unknown_placeholders_are_rejected() {
+        let placeholders = Placeholders::default();
+        assert!(validate_placeholders("{nope}", &placeholders).is_err());
+        assert!(validate_placeholders("{sunrise}", &placeholders).is_err());
+        assert_eq!(placeholders.expand("{nope} {week", &noon()), "{nope} {week");
+    }
+}
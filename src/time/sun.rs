@@ -0,0 +1,220 @@
+// src/time/sun.rs
+// github.com/cvusmo/hyprclock
+
+// Sunrise, sunset and twilight from latitude/longitude alone, after the NOAA sunrise equation.
+// Good to about a minute, which is all a clock needs, and free of GTK so themes can use it too.
+
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+// Sun altitudes in degrees; sunrise allows for refraction and the solar disc
+const SUNRISE_ALTITUDE: f64 = -0.833;
+const CIVIL_ALTITUDE: f64 = -6.0;
+const NAUTICAL_ALTITUDE: f64 = -12.0;
+const ASTRONOMICAL_ALTITUDE: f64 = -18.0;
+
+const J2000: f64 = 2451545.0; // Julian date of 2000-01-01 12:00 UTC
+const UNIX_EPOCH_JULIAN: f64 = 2440587.5;
+const OBLIQUITY: f64 = 23.4397;
+
+// How the sun relates to a given altitude over one day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Crossing {
+    Between(DateTime<Utc>, DateTime<Utc>), // rises above, then sets below
+    AlwaysAbove,                           // polar day for this altitude
+    AlwaysBelow,                           // polar night for this altitude
+}
+
+impl Crossing {
+    pub fn start(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Crossing::Between(start, _) => Some(*start),
+            _ => None,
+        }
+    }
+
+    pub fn end(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Crossing::Between(_, end) => Some(*end),
+            _ => None,
+        }
+    }
+
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        match self {
+            Crossing::Between(start, end) => *start <= time && time < *end,
+            Crossing::AlwaysAbove => true,
+            Crossing::AlwaysBelow => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunTimes {
+    pub noon: DateTime<Utc>,
+    pub daylight: Crossing,     // sunrise to sunset
+    pub civil: Crossing,        // civil dawn to civil dusk
+    pub nautical: Crossing,     // nautical dawn to nautical dusk
+    pub astronomical: Crossing, // astronomical dawn to astronomical dusk
+}
+
+impl SunTimes {
+    // Function to compute the sun's day for a calendar date; longitude is positive east
+    pub fn new(date: NaiveDate, latitude: f64, longitude: f64) -> Self {
+        let days = (date - j2000_date()).num_days() as f64;
+        let mean_solar_noon = days - longitude / 360.0;
+
+        let anomaly = (357.5291 + 0.98560028 * mean_solar_noon).rem_euclid(360.0);
+        let m = anomaly.to_radians();
+        let center = 1.9148 * m.sin() + 0.02 * (2.0 * m).sin() + 0.0003 * (3.0 * m).sin();
+        let ecliptic_longitude = (anomaly + center + 180.0 + 102.9372).rem_euclid(360.0);
+        let lambda = ecliptic_longitude.to_radians();
+
+        let transit = J2000 + mean_solar_noon + 0.0053 * m.sin() - 0.0069 * (2.0 * lambda).sin();
+        let declination = (lambda.sin() * OBLIQUITY.to_radians().sin()).asin();
+
+        let crossing = |altitude: f64| -> Crossing {
+            let phi = latitude.to_radians();
+            let cos_hour_angle = (altitude.to_radians().sin() - phi.sin() * declination.sin())
+                / (phi.cos() * declination.cos());
+            if cos_hour_angle < -1.0 {
+                Crossing::AlwaysAbove
+            } else if cos_hour_angle > 1.0 {
+                Crossing::AlwaysBelow
+            } else {
+                let offset = cos_hour_angle.acos().to_degrees() / 360.0;
+                Crossing::Between(
+                    julian_to_utc(transit - offset),
+                    julian_to_utc(transit + offset),
+                )
+            }
+        };
+
+        SunTimes {
+            noon: julian_to_utc(transit),
+            daylight: crossing(SUNRISE_ALTITUDE),
+            civil: crossing(CIVIL_ALTITUDE),
+            nautical: crossing(NAUTICAL_ALTITUDE),
+            astronomical: crossing(ASTRONOMICAL_ALTITUDE),
+        }
+    }
+
+    pub fn sunrise(&self) -> Option<DateTime<Utc>> {
+        self.daylight.start()
+    }
+
+    pub fn sunset(&self) -> Option<DateTime<Utc>> {
+        self.daylight.end()
+    }
+
+    // Whether the sun is up, for switching between day and night looks
+    pub fn is_day(&self, time: DateTime<Utc>) -> bool {
+        self.daylight.contains(time)
+    }
+}
+
+fn j2000_date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2000, 1, 1).expect("Invalid date")
+}
+
+fn julian_to_utc(julian: f64) -> DateTime<Utc> {
+    let millis = ((julian - UNIX_EPOCH_JULIAN) * 86_400_000.0).round() as i64;
+    Utc.timestamp_millis_opt(millis)
+        .single()
+        .unwrap_or_default()
+}
+
+// Function to check coordinates before they reach the calculation
+pub fn validate_coordinates(latitude: f64, longitude: f64) -> Result<(), String> {
+    if !(-90.0..=90.0).contains(&latitude) {
+        return Err(format!("Latitude must be between -90 and 90: {}", latitude));
+    }
+    if !(-180.0..=180.0).contains(&longitude) {
+        return Err(format!(
+            "Longitude must be between -180 and 180: {}",
+            longitude
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::placeholders::Placeholders;
+
+    const TROMSO: (f64, f64) = (69.6492, 18.9553);
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").expect("Valid date")
+    }
+
+    // NOAA's calculator rounds to the minute
+    fn assert_near(actual: Option<DateTime<Utc>>, expected: &str) {
+        let expected = DateTime::parse_from_rfc3339(expected).expect("Valid time");
+        let actual = actual.expect("Event should happen");
+        let error = (actual - expected.with_timezone(&Utc)).num_seconds().abs();
+        assert!(error <= 60, "{} is {}s from {}", actual, error, expected);
+    }
+
+    #[test]
+    fn london_summer_solstice() {
+        let sun = SunTimes::new(date("2024-06-21"), 51.5074, -0.1278);
+        assert_near(sun.sunrise(), "2024-06-21T03:43:00Z");
+        assert_near(sun.sunset(), "2024-06-21T20:21:00Z");
+        assert_near(Some(sun.noon), "2024-06-21T12:02:00Z");
+    }
+
+    #[test]
+    fn new_york_winter_solstice() {
+        let sun = SunTimes::new(date("2024-12-21"), 40.7128, -74.0060);
+        assert_near(sun.sunrise(), "2024-12-21T12:16:00Z");
+        assert_near(sun.sunset(), "2024-12-21T21:32:00Z");
+        assert_near(Some(sun.noon), "2024-12-21T16:54:00Z");
+    }
+
+    #[test]
+    fn sydney_summer_solstice() {
+        // Sunrise falls on the previous UTC day
+        let sun = SunTimes::new(date("2024-12-21"), -33.8688, 151.2093);
+        assert_near(sun.sunrise(), "2024-12-20T18:41:00Z");
+        assert_near(sun.sunset(), "2024-12-21T09:05:00Z");
+        assert_near(Some(sun.noon), "2024-12-21T01:54:00Z");
+    }
+
+    #[test]
+    fn polar_day() {
+        let sun = SunTimes::new(date("2024-06-21"), TROMSO.0, TROMSO.1);
+        assert_eq!(sun.daylight, Crossing::AlwaysAbove);
+        assert_eq!(sun.sunrise(), None);
+        assert!(sun.is_day(Utc.with_ymd_and_hms(2024, 6, 21, 23, 0, 0).unwrap()));
+
+        let placeholders = Placeholders {
+            coordinates: Some(TROMSO),
+        };
+        let time = glib::DateTime::from_utc(2024, 6, 21, 12, 0, 0.0).expect("Valid date");
+        assert_eq!(
+            placeholders.expand("{sunrise} {sunset} {dusk}", &time),
+            "--:-- --:-- --:--"
+        );
+    }
+
+    #[test]
+    fn polar_night() {
+        let sun = SunTimes::new(date("2024-12-21"), TROMSO.0, TROMSO.1);
+        assert_eq!(sun.daylight, Crossing::AlwaysBelow);
+        assert_eq!(sun.sunset(), None);
+        assert!(!sun.is_day(sun.noon));
+        // Civil twilight still happens around noon
+        assert!(sun.civil.contains(sun.noon));
+
+        let placeholders = Placeholders {
+            coordinates: Some(TROMSO),
+        };
+        let time = glib::DateTime::from_utc(2024, 12, 21, 12, 0, 0.0).expect("Valid date");
+        assert_eq!(
+            placeholders.expand("{sunrise}/{sunset}", &time),
+            "--:--/--:--"
+        );
+        assert_eq!(placeholders.expand("{noon}", &time), "10:42");
+    }
+}
@@ -0,0 +1,176 @@
+// src/time/tick.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::config::Config;
+use crate::configuration::format::conversions;
+use crate::time::placeholders::placeholder_names;
+use glib::{DateTime, SourceId};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+// Smallest unit of time a format string displays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TickUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+}
+
+impl TickUnit {
+    // Function to find the finest unit shown by a format string
+    pub fn from_format(format: &str) -> Self {
+        let placeholder_units = placeholder_names(format)
+            .into_iter()
+            .map(|name| match name {
+                // Illumination moves by about a percent every few hours
+                "moon" | "moon_phase" | "illumination" => TickUnit::Hour,
+                // Weeks and sun events change with the date
+                _ => TickUnit::Day,
+            });
+        conversions(format)
+            .into_iter()
+            .map(|conversion| match conversion {
+                // Microseconds can only be refreshed as often as seconds
+                'S' | 'T' | 'r' | 'c' | 'X' | 'f' => TickUnit::Second,
+                'M' | 'R' => TickUnit::Minute,
+                'H' | 'I' | 'k' | 'l' | 'p' | 'P' => TickUnit::Hour,
+                _ => TickUnit::Day,
+            })
+            .chain(placeholder_units)
+            .min()
+            .unwrap_or(TickUnit::Day)
+    }
+
+    // Function to find the finest unit shown anywhere in the configuration
+    pub fn from_config(config: &Config) -> Self {
+        let mut units = Vec::new();
+        if config.general.shows_digital() {
+            units.push(config.general.formatter().tick_unit());
+        }
+        if config.general.shows_analog() {
+            units.push(if config.analog.second_hand {
+                TickUnit::Second
+            } else {
+                TickUnit::Minute
+            });
+        }
+        if config.date.enabled {
+            units.push(Self::from_format(&config.date.format));
+        }
+        units.extend(
+            config
+                .zones
+                .iter()
+                .map(|zone| zone.formatter(&config.general).tick_unit()),
+        );
+
+        let unit = units.into_iter().min().unwrap_or(TickUnit::Day);
+
+        // Zones with :30 or :45 offsets flip hours and days off the local boundary
+        if config.zones.is_empty() {
+            unit
+        } else {
+            unit.min(TickUnit::Minute)
+        }
+    }
+
+    // Function to compute the delay until the next boundary of this unit
+    pub fn delay_until_next(&self, now: &DateTime) -> Duration {
+        let micros_into_second = now.microsecond() as u64;
+        let seconds_into_unit = match self {
+            TickUnit::Second => 0,
+            TickUnit::Minute => now.second() as u64,
+            TickUnit::Hour => (now.minute() * 60 + now.second()) as u64,
+            TickUnit::Day => return Self::delay_until_midnight(now),
+        };
+        let unit_micros = match self {
+            TickUnit::Second => 1_000_000,
+            TickUnit::Minute => 60_000_000,
+            _ => 3_600_000_000,
+        };
+        let elapsed = seconds_into_unit * 1_000_000 + micros_into_second;
+
+        // Wake slightly after the boundary so the new value is already current
+        Duration::from_micros(unit_micros - elapsed.min(unit_micros - 1)) + Duration::from_millis(5)
+    }
+
+    fn delay_until_midnight(now: &DateTime) -> Duration {
+        let midnight = DateTime::from_local(now.year(), now.month(), now.day_of_month(), 0, 0, 0.0)
+            .and_then(|today| today.add_days(1));
+        match midnight {
+            Ok(midnight) => {
+                Duration::from_micros(midnight.difference(now).as_microseconds().max(0) as u64)
+                    + Duration::from_millis(5)
+            }
+            Err(_) => Duration::from_secs(60),
+        }
+    }
+}
+
+type Subscriber = Box<dyn FnMut()>;
+
+// Scheduler that re-arms itself for each wall-clock boundary
+#[derive(Clone)]
+pub struct Ticker {
+    unit: TickUnit,
+    subscribers: Rc<RefCell<Vec<Subscriber>>>,
+    running: Rc<Cell<bool>>,
+    source: Rc<RefCell<Option<SourceId>>>,
+}
+
+impl Ticker {
+    pub fn new(unit: TickUnit) -> Self {
+        Ticker {
+            unit,
+            subscribers: Rc::new(RefCell::new(Vec::new())),
+            running: Rc::new(Cell::new(false)),
+            source: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    pub fn for_config(config: &Config) -> Self {
+        Self::new(TickUnit::from_config(config))
+    }
+
+    pub fn unit(&self) -> TickUnit {
+        self.unit
+    }
+
+    // Register a callback to run on every tick
+    pub fn subscribe<F: FnMut() + 'static>(&self, callback: F) {
+        self.subscribers.borrow_mut().push(Box::new(callback));
+    }
+
+    // Arm the first timeout; each tick arms the next one until stopped
+    pub fn start(&self) {
+        if !self.running.replace(true) {
+            self.arm();
+        }
+    }
+
+    pub fn stop(&self) {
+        self.running.set(false);
+        if let Some(source) = self.source.borrow_mut().take() {
+            source.remove();
+        }
+    }
+
+    fn arm(&self) {
+        let now = DateTime::now_local().expect("Failed to get local time");
+        let delay = self.unit.delay_until_next(&now);
+        let ticker = self.clone();
+        let source = glib::timeout_add_local_once(delay, move || {
+            ticker.source.borrow_mut().take();
+            for callback in ticker.subscribers.borrow_mut().iter_mut() {
+                callback();
+            }
+            // A subscriber may have stopped or restarted the ticker
+            if ticker.running.get() && ticker.source.borrow().is_none() {
+                ticker.arm();
+            }
+        });
+        *self.source.borrow_mut() = Some(source);
+    }
+}
@@ -0,0 +1,362 @@
+// src/time/tooltip.rs
+// github.com/cvusmo/hyprclock
+
+// Text for bar tooltips and the calendar popover, built without any GTK widget so bar and CLI
+// modes never need a display.
+
+use crate::configuration::config::Config;
+use crate::configuration::general::DEFAULT_DATE_FORMAT;
+use crate::time::astronomy::{month_events, next_full_moon, next_new_moon, AstroEvent, MoonPhase};
+use crate::time::grid::{DayCell, MonthGrid};
+use crate::time::holidays::HolidayCalendar;
+use crate::time::locale::weekday_headers;
+use crate::time::placeholders::Placeholders;
+use crate::tools::calendar::{shift_month, CalendarView};
+use crate::tools::events::{upcoming, CalendarEvent};
+use chrono::{Datelike, NaiveDate, Utc, Weekday};
+use glib::DateTime;
+
+// Sun events for a day, expanded through the label placeholders
+const SUN_SUMMARY: &str = "Sunrise {sunrise}  Sunset {sunset}  Noon {noon}\n\
+Civil {dawn} – {dusk}\n\
+Nautical {nautical_dawn} – {nautical_dusk}\n\
+Astronomical {astronomical_dawn} – {astronomical_dusk}";
+
+// Function to build the bar tooltip: time, date, calendar months, holidays, upcoming events,
+// zones, moon and sun
+pub fn generate_tooltip(
+    config: &Config,
+    view: &CalendarView,
+    now: &DateTime,
+    events: &[CalendarEvent],
+) -> String {
+    let year = now.year();
+    let month = now.month() as u32;
+    let day = now.day_of_month() as u32;
+    let date = if config.date.enabled {
+        config.date.format_date(now, &config.general.placeholders())
+    } else {
+        now.format(DEFAULT_DATE_FORMAT)
+            .expect("Format failed")
+            .to_string()
+    };
+    let holidays = config.holidays.calendar();
+    let today = NaiveDate::from_ymd_opt(year, month, day).unwrap_or_default();
+    let (shown_year, shown_month) = view.month(year, month);
+    let mut tooltip = format!(
+        "<big>{}</big>\n{}\n<tt><small>{}</small></tt>",
+        glib::markup_escape_text(&config.general.format_time(now)),
+        glib::markup_escape_text(&date),
+        generate_months(
+            shown_year,
+            shown_month,
+            today,
+            holidays,
+            events,
+            config,
+            view.offset != 0
+        )
+    );
+    tooltip.push_str(&glib::markup_escape_text(&describe_holidays(
+        holidays,
+        shown_year,
+        shown_month,
+    )));
+    tooltip.push_str(&generate_upcoming(events, config));
+    tooltip.push_str(&generate_zones(config));
+    tooltip.push_str(&generate_astronomy(shown_year, shown_month));
+
+    let placeholders = config.general.placeholders();
+    if placeholders.coordinates.is_some() {
+        tooltip.push_str(&format!(
+            "\n{}",
+            glib::markup_escape_text(&generate_sun(&placeholders, now))
+        ));
+    }
+    tooltip
+}
+
+// Function to list the next events from the ICS file under an "Upcoming" heading
+fn generate_upcoming(events: &[CalendarEvent], config: &Config) -> String {
+    let next = upcoming(
+        events,
+        chrono::Local::now(),
+        config.calendar.upcoming_days,
+        config.calendar.upcoming,
+    );
+    if next.is_empty() {
+        return String::new();
+    }
+    let mut lines = String::from("\n<b>Upcoming</b>");
+    for event in next {
+        lines.push_str(&format!(
+            "\n{}  {} <i>{}</i>",
+            glib::markup_escape_text(&event.when()),
+            glib::markup_escape_text(&event.summary),
+            glib::markup_escape_text(&event.calendar)
+        ));
+    }
+    lines
+}
+
+fn generate_astronomy(year: i32, month: u32) -> String {
+    let now = Utc::now();
+    let phase = MoonPhase::at(now);
+    let mut lines = format!(
+        "\n{} {}, {:.0}% lit\nFull moon {}  New moon {}",
+        phase.glyph(),
+        phase.name(),
+        phase.illumination * 100.0,
+        next_full_moon(now)
+            .with_timezone(&chrono::Local)
+            .format("%a %d %b %H:%M"),
+        next_new_moon(now)
+            .with_timezone(&chrono::Local)
+            .format("%a %d %b %H:%M")
+    );
+    lines.push_str(&glib::markup_escape_text(&describe_events(year, month)));
+    lines
+}
+
+// Function to list a month's moon phases and seasons, one per line
+pub fn describe_events(year: i32, month: u32) -> String {
+    month_events(year, month)
+        .iter()
+        .map(|(time, event)| {
+            format!(
+                "\n{} {} {}",
+                event.glyph(),
+                event.name(),
+                time.format("%a %d %H:%M")
+            )
+        })
+        .collect()
+}
+
+// Function to list a month's holidays, one per line
+pub fn describe_holidays(holidays: &HolidayCalendar, year: i32, month: u32) -> String {
+    holidays
+        .in_month(year, month)
+        .iter()
+        .map(|holiday| format!("\n★ {} {}", holiday.name, holiday.date.format("%a %d")))
+        .collect()
+}
+
+pub fn generate_sun(placeholders: &Placeholders, day: &DateTime) -> String {
+    placeholders.expand(SUN_SUMMARY, day)
+}
+
+fn generate_zones(config: &Config) -> String {
+    config
+        .zones
+        .iter()
+        .map(|zone| {
+            format!(
+                "\n<b>{}</b>  {}",
+                glib::markup_escape_text(&zone.label),
+                glib::markup_escape_text(&zone.get_current_time(&config.general))
+            )
+        })
+        .collect()
+}
+
+// Function to lay out the configured number of months around the shown one, side by side
+fn generate_months(
+    year: i32,
+    month: u32,
+    today: NaiveDate,
+    holidays: &HolidayCalendar,
+    events: &[CalendarEvent],
+    config: &Config,
+    titled: bool,
+) -> String {
+    let count = config.calendar.months;
+    if count == 1 && !titled {
+        return generate_calendar(month, year, today, holidays, events, config);
+    }
+
+    let (first_year, first_month) = if count == 12 {
+        (year, 1)
+    } else {
+        shift_month(year, month, -((count as i32 - 1) / 2))
+    };
+    let blocks: Vec<(usize, Vec<String>)> = (0..count)
+        .map(|i| {
+            let (y, m) = shift_month(first_year, first_month, i as i32);
+            let grid = generate_calendar(m, y, today, holidays, events, config);
+            let mut lines: Vec<String> = grid.lines().map(String::from).collect();
+            let width = lines
+                .iter()
+                .map(|line| visible_width(line))
+                .max()
+                .unwrap_or_default();
+            lines.insert(0, month_title(y, m, width));
+            (width, lines)
+        })
+        .collect();
+
+    blocks
+        .chunks(config.calendar.months_per_row as usize)
+        .map(|row| {
+            let height = row.iter().map(|(_, lines)| lines.len()).max();
+            (0..height.unwrap_or_default())
+                .map(|i| {
+                    let cells: Vec<String> = row
+                        .iter()
+                        .map(|(width, lines)| {
+                            let line = lines.get(i).map_or("", String::as_str);
+                            let padding = width.saturating_sub(visible_width(line));
+                            format!("{}{}", line, " ".repeat(padding))
+                        })
+                        .collect();
+                    cells.join("   ").trim_end().to_string()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+// Function to centre a localized month name and year over a grid
+fn month_title(year: i32, month: u32, width: usize) -> String {
+    let title = DateTime::from_local(year, month as i32, 1, 12, 0, 0.0)
+        .and_then(|date| date.format("%B %Y"))
+        .map(|title| title.to_string())
+        .unwrap_or_default();
+    let padding = width.saturating_sub(title.chars().count()) / 2;
+    format!(
+        "{}<b>{}</b>",
+        " ".repeat(padding),
+        glib::markup_escape_text(&title)
+    )
+}
+
+// Function to count the columns a line of markup takes up once rendered
+fn visible_width(markup: &str) -> usize {
+    let mut width = 0;
+    let mut in_tag = false;
+    let mut in_entity = false;
+    for c in markup.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            '&' if !in_tag => {
+                in_entity = true;
+                width += 1;
+            }
+            ';' if in_entity => in_entity = false,
+            _ if in_tag || in_entity => {}
+            _ => width += 1,
+        }
+    }
+    width
+}
+
+// Function to build a month's grid with its holidays, events, moon phases and seasons
+pub fn month_grid(
+    year: i32,
+    month: u32,
+    week_start: Weekday,
+    holidays: &HolidayCalendar,
+    events: &[CalendarEvent],
+) -> Option<MonthGrid> {
+    let mut grid = MonthGrid::new(year, month, week_start)?;
+    grid.add_events(events.iter().map(|event| event.date()));
+    grid.add_events(
+        month_events(year, month)
+            .iter()
+            .map(|(time, _)| time.date_naive()),
+    );
+    grid.mark_holidays(
+        holidays
+            .in_month(year, month)
+            .iter()
+            .map(|holiday| holiday.date),
+    );
+    Some(grid)
+}
+
+fn generate_calendar(
+    month: u32,
+    year: i32,
+    today: NaiveDate,
+    holidays: &HolidayCalendar,
+    calendar_events: &[CalendarEvent],
+    config: &Config,
+) -> String {
+    // Weekday names come from LC_TIME, the first column from the config or the locale.
+    let week_start = config.calendar.first_weekday();
+    let Some(mut grid) = month_grid(year, month, week_start, holidays, calendar_events) else {
+        return String::new();
+    };
+    grid.mark_today(today);
+    let colors = &config.theme.calendar;
+
+    let headers: Vec<String> = weekday_headers(week_start)
+        .into_iter()
+        .zip(grid.weekdays())
+        .map(|(name, weekday)| match weekday {
+            Weekday::Sat | Weekday::Sun => colored(&name, &colors.weekends),
+            _ => name,
+        })
+        .collect();
+    let mut lines = vec![headers.join("  ")];
+    if config.calendar.week_numbers {
+        lines[0].insert_str(0, "Wk ");
+    }
+
+    let events = month_events(year, month);
+    for week in &grid.weeks {
+        let mut line = String::new();
+        if config.calendar.week_numbers {
+            line.push_str(&format!("{:2} ", week.iso_week));
+        }
+        let cells: Vec<String> = week
+            .days
+            .iter()
+            .map(|cell| render_cell(cell, &events, config))
+            .collect();
+        line.push_str(&cells.join(" "));
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+// Function to draw one three-column day cell with its Pango colors
+fn render_cell(
+    cell: &DayCell,
+    events: &[(chrono::DateTime<chrono::Local>, AstroEvent)],
+    config: &Config,
+) -> String {
+    let colors = &config.theme.calendar;
+    let day = cell.date.day();
+    if !cell.in_month {
+        return colored(&format!("{:2} ", day), &colors.outside);
+    }
+
+    // Moon phases and seasons replace the gap after the day number
+    let mark = events
+        .iter()
+        .find(|(time, _)| time.date_naive() == cell.date)
+        .map_or(' ', |(_, event)| event.mark());
+    // Holidays are underlined, which keeps the columns aligned
+    let text = if cell.holiday {
+        format!("<u>{:2}</u>{}", day, mark)
+    } else {
+        format!("{:2}{}", day, mark)
+    };
+    if cell.today {
+        format!("<b>{}</b>", colored(&text, &colors.today))
+    } else if cell.has_events() || cell.holiday {
+        colored(&text, &colors.events)
+    } else if cell.is_weekend() {
+        colored(&text, &colors.weekends)
+    } else {
+        text
+    }
+}
+
+fn colored(text: &str, color: &str) -> String {
+    format!("<span foreground=\"{}\">{}</span>", color, text)
+}
@@ -0,0 +1,218 @@
+// src/tools/alarms.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::alarms::AlarmConfig;
+use crate::configuration::config::get_state_dir;
+use chrono::{DateTime, Datelike, Duration, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+// An alarm counts as ringing, rather than missed, if it is noticed within this many seconds
+const GRACE_SECONDS: i64 = 90;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Snooze {
+    pub alarm: AlarmConfig,
+    pub until: i64, // unix seconds
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Ringing {
+    pub label: String,
+    pub since: i64, // unix seconds
+}
+
+// Runtime alarms and bookkeeping, persisted so nothing is lost across restarts
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AlarmStore {
+    #[serde(default)]
+    pub alarms: Vec<AlarmConfig>,
+    #[serde(default)]
+    pub snoozes: Vec<Snooze>,
+    #[serde(default)]
+    pub ringing: Vec<Ringing>,
+    pub last_checked: Option<i64>,
+}
+
+// Result of one pass over the alarms
+#[derive(Debug, Default)]
+pub struct AlarmCheck {
+    pub ringing: Vec<AlarmConfig>,
+    pub missed: Vec<(AlarmConfig, DateTime<Local>)>,
+}
+
+impl AlarmStore {
+    /// Load runtime alarms from the state file
+    pub fn load() -> Self {
+        fs::read_to_string(get_alarms_path())
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save runtime alarms to the state file
+    pub fn save(&self) -> io::Result<()> {
+        let path = get_alarms_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+
+    // Add a runtime alarm, replacing any alarm with the same label
+    pub fn add(&mut self, alarm: AlarmConfig) {
+        self.remove(&alarm.label);
+        self.alarms.push(alarm);
+        self.alarms.sort_by(|a, b| a.time.cmp(&b.time));
+    }
+
+    pub fn remove(&mut self, label: &str) -> bool {
+        let count = self.alarms.len();
+        self.alarms.retain(|alarm| alarm.label != label);
+        self.alarms.len() != count
+    }
+
+    pub fn snooze(&mut self, alarm: &AlarmConfig, now: i64) {
+        self.stop_ringing(&alarm.label);
+        self.snoozes.push(Snooze {
+            alarm: alarm.clone(),
+            until: now + alarm.snooze_minutes as i64 * 60,
+        });
+    }
+
+    pub fn start_ringing(&mut self, label: &str, now: i64) {
+        self.stop_ringing(label);
+        self.ringing.push(Ringing {
+            label: label.to_string(),
+            since: now,
+        });
+    }
+
+    pub fn stop_ringing(&mut self, label: &str) {
+        self.ringing.retain(|ringing| ringing.label != label);
+    }
+
+    pub fn is_ringing(&self) -> bool {
+        !self.ringing.is_empty()
+    }
+
+    // Function to find alarms due since the last check, splitting them into ringing and missed
+    pub fn check(&mut self, configured: &[AlarmConfig], now: DateTime<Local>) -> AlarmCheck {
+        let mut result = AlarmCheck::default();
+        let now_unix = now.timestamp();
+
+        // The first run has nothing to catch up on
+        let since = self
+            .last_checked
+            .and_then(|last| Local.timestamp_opt(last, 0).single())
+            .unwrap_or(now);
+
+        for alarm in configured.iter().chain(self.alarms.iter()) {
+            if !alarm.enabled {
+                continue;
+            }
+            for due in occurrences(alarm, since, now) {
+                if now_unix - due.timestamp() <= GRACE_SECONDS {
+                    result.ringing.push(alarm.clone());
+                } else {
+                    result.missed.push((alarm.clone(), due));
+                }
+            }
+        }
+
+        let (due, waiting): (Vec<Snooze>, Vec<Snooze>) = self
+            .snoozes
+            .drain(..)
+            .partition(|snooze| snooze.until <= now_unix);
+        self.snoozes = waiting;
+        result
+            .ringing
+            .extend(due.into_iter().map(|snooze| snooze.alarm));
+
+        // Ringing left over from a previous session can no longer be answered
+        if self
+            .last_checked
+            .is_none_or(|last| now_unix - last > GRACE_SECONDS)
+        {
+            self.ringing.clear();
+        }
+
+        self.last_checked = Some(now_unix);
+        result
+    }
+
+    // The next time any alarm will ring, for display
+    pub fn next_alarm(
+        &self,
+        configured: &[AlarmConfig],
+        now: DateTime<Local>,
+    ) -> Option<(AlarmConfig, DateTime<Local>)> {
+        let horizon = now + Duration::days(8);
+        let scheduled = configured
+            .iter()
+            .chain(self.alarms.iter())
+            .filter(|alarm| alarm.enabled)
+            .filter_map(|alarm| {
+                occurrences(alarm, now, horizon)
+                    .into_iter()
+                    .next()
+                    .map(|due| (alarm.clone(), due))
+            });
+        let snoozed = self.snoozes.iter().filter_map(|snooze| {
+            Local
+                .timestamp_opt(snooze.until, 0)
+                .single()
+                .map(|due| (snooze.alarm.clone(), due))
+        });
+        scheduled.chain(snoozed).min_by_key(|(_, due)| *due)
+    }
+}
+
+/// Get the path of the alarms state file
+pub fn get_alarms_path() -> PathBuf {
+    get_state_dir().join("alarms.json")
+}
+
+// Function to list the times an alarm rings in the window (after, until]
+pub fn occurrences(
+    alarm: &AlarmConfig,
+    after: DateTime<Local>,
+    until: DateTime<Local>,
+) -> Vec<DateTime<Local>> {
+    let Some(time) = alarm.time_of_day() else {
+        return Vec::new();
+    };
+
+    let mut found = Vec::new();
+    let mut day = after.date_naive();
+    while day <= until.date_naive() {
+        if alarm.rings_on(day.weekday()) {
+            // Skip times that do not exist on DST transition days
+            if let Some(due) = Local.from_local_datetime(&day.and_time(time)).earliest() {
+                if due > after && due <= until {
+                    found.push(due);
+                }
+            }
+        }
+        match day.succ_opt() {
+            Some(next) => day = next,
+            None => break,
+        }
+    }
+    found
+}
+
+pub fn format_alarm(alarm: &AlarmConfig) -> String {
+    if alarm.weekdays.is_empty() {
+        format!("{} {}", alarm.time, alarm.label)
+    } else {
+        format!(
+            "{} {} ({})",
+            alarm.time,
+            alarm.label,
+            alarm.weekdays.join(", ")
+        )
+    }
+}
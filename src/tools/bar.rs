@@ -0,0 +1,354 @@
+// src/tools/bar.rs
+// github.com/cvusmo/hyprclock
+
+// Everything a status bar shows for one update, gathered without GTK, and the backends that
+// print it for each bar
+
+use crate::configuration::config::Config;
+use crate::configuration::waybar::CLASS_RULES;
+use crate::time::tooltip::generate_tooltip;
+use crate::tools::alarms::AlarmStore;
+use crate::tools::calendar::CalendarView;
+use crate::tools::events::CalendarEvent;
+use crate::tools::pomodoro::{collect_phase_changes, update_pomodoro};
+use crate::tools::stopwatch::{format_centiseconds, now_precise, Stopwatch};
+use crate::tools::timers::{collect_finished, format_timer, now_unix};
+use crate::tools::waybar;
+use glib::DateTime;
+use serde_json::json;
+use std::process::{Command, Stdio};
+
+// Bars accepted by `--bar`
+pub const BAR_BACKENDS: [&str; 5] = ["waybar", "i3bar", "polybar", "eww", "yambar"];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BarStatus {
+    pub text: String,    // Pango markup, as waybar reads it
+    pub tooltip: String, // Pango markup
+    pub alt: String,     // the display mode
+    pub classes: Vec<&'static str>,
+    pub percentage: Option<u8>,
+    pub live: bool, // a timer, the stopwatch or the pomodoro counts by the second
+}
+
+impl BarStatus {
+    // Function to build one update, firing any timers and pomodoro phases that are due
+    pub fn collect(config: &Config, events: &[CalendarEvent]) -> Self {
+        let time = DateTime::now_local().expect("Failed to get local time");
+        let local_now = chrono::Local::now();
+
+        let mut text = clock_text(config, &time);
+        let mut tooltip = generate_tooltip(config, &CalendarView::load(), &time, events);
+
+        // Fire any timers that ran out, then show the soonest remaining one.
+        let (timers, _) = collect_finished(&config.timers);
+        let now = now_unix();
+        if let Some(timer) = timers.soonest() {
+            text = format!(
+                "{}  {}",
+                text,
+                glib::markup_escape_text(&format_timer(timer, now))
+            );
+        }
+        let stopwatch = Stopwatch::load();
+        if !stopwatch.is_idle() {
+            let elapsed = format_centiseconds(stopwatch.elapsed(now_precise()));
+            text = format!("{}  ⏱ {}", text, elapsed);
+            tooltip.push_str(&format!("\nStopwatch {}", elapsed));
+            for (number, lap, _) in stopwatch.lap_times() {
+                tooltip.push_str(&format!("\n  Lap {} {}", number, format_centiseconds(lap)));
+            }
+        }
+        for timer in &timers.timers {
+            tooltip.push_str(&format!(
+                "\n{}",
+                glib::markup_escape_text(&format_timer(timer, now))
+            ));
+        }
+
+        // Classes and percentage follow the [waybar] rules; a running pomodoro adds its phase
+        // and takes over the percentage
+        let alarms = AlarmStore::load();
+        let mut classes = waybar::classes(config, local_now, events, !alarms.ringing.is_empty());
+        let mut percentage = waybar::percentage(config, local_now);
+        let (pomodoro, _) = collect_phase_changes(&config.pomodoro);
+        if pomodoro.is_running() {
+            text = format!(
+                "{}  {}",
+                text,
+                glib::markup_escape_text(&pomodoro.status(now))
+            );
+            tooltip.push_str(&format!(
+                "\n{} {}/{}",
+                pomodoro.phase.name(),
+                pomodoro.completed,
+                config.pomodoro.cycles
+            ));
+            classes.push(pomodoro.phase.css_class());
+            percentage = Some((pomodoro.progress(now) * 100.0).round() as u8);
+        }
+
+        // The window owns alarm checks; the bar only reports them
+        for ringing in &alarms.ringing {
+            text = format!("⏰ {}  {}", glib::markup_escape_text(&ringing.label), text);
+        }
+        if let Some((alarm, due)) = alarms.next_alarm(&config.alarms, local_now) {
+            tooltip.push_str(&format!(
+                "\nNext alarm {} {}",
+                due.format("%a %H:%M"),
+                glib::markup_escape_text(&alarm.label)
+            ));
+        }
+
+        BarStatus {
+            text,
+            tooltip,
+            alt: config.general.display.clone(),
+            classes,
+            percentage,
+            live: !timers.timers.is_empty() || stopwatch.is_running() || pomodoro.is_running(),
+        }
+    }
+
+    // Function to build the JSON object waybar reads for one update
+    pub fn to_waybar(&self) -> serde_json::Value {
+        // serde_json escapes any control characters
+        let mut output = json!({
+            "text": self.text,
+            "tooltip": self.tooltip,
+            "alt": self.alt
+        });
+        if !self.classes.is_empty() {
+            output["class"] = json!(self.classes);
+        }
+        if let Some(percentage) = self.percentage {
+            output["percentage"] = json!(percentage);
+        }
+        output
+    }
+}
+
+// Function to format the clock for the markup text; formats and locale names can hold `&` or `<`
+fn clock_text(config: &Config, time: &DateTime) -> String {
+    glib::markup_escape_text(&config.general.format_time(time)).to_string()
+}
+
+// Common interface for every bar protocol
+pub trait BarBackend {
+    // Printed once before the first update of a stream
+    fn header(&self) -> Option<String> {
+        None
+    }
+
+    // One update, without the trailing newline
+    fn render(&self, status: &BarStatus) -> String;
+
+    // Whether the bar only understands a long-running process
+    fn streams(&self) -> bool {
+        false
+    }
+
+    // Whether the bar writes click events to stdin
+    fn reads_clicks(&self) -> bool {
+        false
+    }
+
+    // Function to act on one line from stdin; the next update follows right away
+    fn handle_input(&self, _line: &str, _config: &Config) {}
+}
+
+// Function to pick the backend for a bar name
+pub fn backend_for(bar: &str) -> Box<dyn BarBackend> {
+    match bar {
+        "i3bar" => Box::new(I3barBackend),
+        "polybar" => Box::new(PolybarBackend),
+        "eww" => Box::new(EwwBackend),
+        "yambar" => Box::new(YambarBackend),
+        _ => Box::new(WaybarBackend),
+    }
+}
+
+// JSON for a `return-type: json` custom module
+pub struct WaybarBackend;
+
+impl BarBackend for WaybarBackend {
+    fn render(&self, status: &BarStatus) -> String {
+        status.to_waybar().to_string()
+    }
+}
+
+// The i3bar protocol, also read by swaybar: a header, then an endless JSON array with one
+// block list per update
+pub struct I3barBackend;
+
+impl BarBackend for I3barBackend {
+    fn header(&self) -> Option<String> {
+        // Opening with an empty update lets every later one start with a comma
+        Some(format!(
+            "{}\n[\n[]",
+            json!({ "version": 1, "click_events": true })
+        ))
+    }
+
+    fn render(&self, status: &BarStatus) -> String {
+        let block = json!({
+            "name": "hyprclock",
+            "instance": status.alt,
+            "full_text": status.text,
+            "markup": "pango",
+            "urgent": status.classes.contains(&"alarm")
+        });
+        format!(",[{}]", block)
+    }
+
+    fn streams(&self) -> bool {
+        true
+    }
+
+    fn reads_clicks(&self) -> bool {
+        true
+    }
+
+    // Left click opens the window, right click starts or stops the pomodoro
+    fn handle_input(&self, line: &str, config: &Config) {
+        // Clicks arrive as an endless array too, so drop the brackets and separators
+        let line = line.trim().trim_start_matches(['[', ',']);
+        let Ok(click) = serde_json::from_str::<serde_json::Value>(line) else {
+            return;
+        };
+        if click["name"] != "hyprclock" {
+            return;
+        }
+        match click["button"].as_u64() {
+            Some(1) => open_window(),
+            Some(3) => toggle_pomodoro(config),
+            _ => {}
+        }
+    }
+}
+
+// Text with formatting tags for a `custom/script` module
+pub struct PolybarBackend;
+
+impl BarBackend for PolybarBackend {
+    fn render(&self, status: &BarStatus) -> String {
+        let mut text = plain_text(&status.text).replace('%', "%%");
+        if status.classes.contains(&"in-meeting") {
+            text = format!("%{{+u}}{}%{{-u}}", text);
+        }
+        if status.classes.contains(&"alarm") {
+            text = format!("%{{R}}{}%{{R}}", text);
+        }
+        format!(
+            "%{{A1:hyprclock:}}%{{A3:hyprclock --pomodoro toggle:}}{}%{{A}}%{{A}}",
+            text
+        )
+    }
+}
+
+// JSON for `defpoll`, or `deflisten` with --stream; eww tooltips are plain text
+pub struct EwwBackend;
+
+impl BarBackend for EwwBackend {
+    fn render(&self, status: &BarStatus) -> String {
+        json!({
+            "text": plain_text(&status.text),
+            "tooltip": plain_text(&status.tooltip),
+            "tooltip_markup": status.tooltip,
+            "alt": status.alt,
+            "class": status.classes.join(" "),
+            "percentage": status.percentage
+        })
+        .to_string()
+    }
+}
+
+// yambar's script protocol: one `tag|type|value` line per tag, ended by an empty line
+pub struct YambarBackend;
+
+impl BarBackend for YambarBackend {
+    fn render(&self, status: &BarStatus) -> String {
+        let mut lines = vec![
+            format!("text|string|{}", single_line(&plain_text(&status.text))),
+            format!("alt|string|{}", status.alt),
+        ];
+        // Tag names cannot hold dashes, so the classes become booleans with underscores
+        for class in CLASS_RULES {
+            lines.push(format!(
+                "{}|bool|{}",
+                class.replace('-', "_"),
+                status.classes.contains(&class)
+            ));
+        }
+        lines.push(format!(
+            "percentage|range:0-100|{}",
+            status.percentage.unwrap_or(0)
+        ));
+        lines.join("\n") + "\n"
+    }
+
+    fn streams(&self) -> bool {
+        true
+    }
+}
+
+// Function to launch the clock window, kept off the bar's stdout
+fn open_window() {
+    let program = std::env::current_exe().unwrap_or_else(|_| "hyprclock".into());
+    if let Err(e) = Command::new(program)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .spawn()
+    {
+        eprintln!("Failed to open the window: {}", e);
+    }
+}
+
+fn toggle_pomodoro(config: &Config) {
+    let (_, errors) = update_pomodoro(&config.pomodoro, |pomodoro| {
+        pomodoro.toggle(now_unix(), &config.pomodoro)
+    });
+    for e in errors {
+        eprintln!("{}", e);
+    }
+}
+
+fn single_line(text: &str) -> String {
+    text.replace('\n', " ")
+}
+
+// Function to drop Pango tags and entities for bars that only show plain text
+fn plain_text(markup: &str) -> String {
+    let mut text = String::with_capacity(markup.len());
+    let mut in_tag = false;
+    for c in markup.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_text_is_escaped() {
+        let mut config = Config::new();
+        config.general.format = Some("%H:%M & <co>".to_string());
+        let time = DateTime::from_utc(2024, 1, 1, 12, 34, 0.0).expect("Valid date");
+
+        let text = clock_text(&config, &time);
+        assert_eq!(text, "12:34 &amp; &lt;co&gt;");
+        assert_eq!(plain_text(&text), "12:34 & <co>");
+    }
+}
@@ -0,0 +1,60 @@
+// src/tools/calendar.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::config::get_state_dir;
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+// Which month the waybar tooltip shows, relative to the current one
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CalendarView {
+    pub offset: i32, // months, negative for the past
+}
+
+impl CalendarView {
+    /// Load the view from the state file
+    pub fn load() -> Self {
+        fs::read_to_string(get_calendar_view_path())
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the view to the state file
+    pub fn save(&self) -> io::Result<()> {
+        let path = get_calendar_view_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+
+    // Function to apply a --waybar-action
+    pub fn apply(&mut self, action: &str) -> Result<(), String> {
+        match action {
+            "next-month" => self.offset += 1,
+            "prev-month" => self.offset -= 1,
+            "reset" => self.offset = 0,
+            _ => return Err(format!("Unknown waybar action: {}", action)),
+        }
+        Ok(())
+    }
+
+    // Function to find the shown year and month from the current ones
+    pub fn month(&self, year: i32, month: u32) -> (i32, u32) {
+        shift_month(year, month, self.offset)
+    }
+}
+
+// Function to move a year and month by a number of months
+pub fn shift_month(year: i32, month: u32, months: i32) -> (i32, u32) {
+    let index = year * 12 + month as i32 - 1 + months;
+    (index.div_euclid(12), index.rem_euclid(12) as u32 + 1)
+}
+
+/// Get the path to the tooltip calendar's state file
+pub fn get_calendar_view_path() -> PathBuf {
+    get_state_dir().join("calendar.json")
+}
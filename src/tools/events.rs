@@ -0,0 +1,151 @@
+// src/tools/events.rs
+// github.com/cvusmo/hyprclock
+
+// Events from the ICS file the schedule dialog reads and writes. Recurring events are only
+// listed on their first occurrence.
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+use icalendar::{Calendar as ICalendar, CalendarDateTime, Component, DatePerhapsTime};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub start: DateTime<Local>,
+    pub end: Option<DateTime<Local>>, // DTEND, when given
+    pub all_day: bool,
+    pub summary: String,
+    pub calendar: String, // X-WR-CALNAME, or the file name
+}
+
+impl CalendarEvent {
+    pub fn date(&self) -> NaiveDate {
+        self.start.date_naive()
+    }
+
+    // Whether a timed event is going on at this instant
+    pub fn is_happening(&self, now: DateTime<Local>) -> bool {
+        !self.all_day && self.start <= now && self.end.is_some_and(|end| now < end)
+    }
+
+    // Function to show the day and time, e.g. "Mon 19 09:30" or "Mon 19 all day"
+    pub fn when(&self) -> String {
+        if self.all_day {
+            format!("{} all day", self.start.format("%a %d"))
+        } else {
+            self.start.format("%a %d %H:%M").to_string()
+        }
+    }
+}
+
+/// Get the path to the ICS file
+pub fn get_ics_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("/"))
+        .join(".thunderbird/calendar.ics")
+}
+
+// Function to read every event from the ICS file, in start order
+pub fn load_events() -> Vec<CalendarEvent> {
+    load_events_from(&get_ics_path())
+}
+
+pub fn load_events_from(path: &Path) -> Vec<CalendarEvent> {
+    let Some(calendar) = fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| contents.parse::<ICalendar>().ok())
+    else {
+        return Vec::new();
+    };
+    let name = calendar
+        .get_name()
+        .map(String::from)
+        .or_else(|| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        })
+        .unwrap_or_default();
+
+    let mut events: Vec<CalendarEvent> = calendar
+        .components
+        .iter()
+        .filter_map(|component| component.as_event())
+        .filter_map(|event| {
+            let (start, all_day) = match event.get_start()? {
+                DatePerhapsTime::Date(date) => (local_time(date.and_hms_opt(0, 0, 0)?)?, true),
+                DatePerhapsTime::DateTime(date_time) => (to_local(date_time)?, false),
+            };
+            let end = match event.get_end() {
+                Some(DatePerhapsTime::Date(date)) => local_time(date.and_hms_opt(0, 0, 0)?),
+                Some(DatePerhapsTime::DateTime(date_time)) => to_local(date_time),
+                None => None,
+            };
+            Some(CalendarEvent {
+                start,
+                end,
+                all_day,
+                summary: event.get_summary().unwrap_or("Unnamed event").to_string(),
+                calendar: name.clone(),
+            })
+        })
+        .collect();
+    events.sort_by_key(|event| event.start);
+    events
+}
+
+// Function to pick the events starting on a local date
+pub fn events_on(events: &[CalendarEvent], date: NaiveDate) -> Vec<&CalendarEvent> {
+    events.iter().filter(|event| event.date() == date).collect()
+}
+
+// Function to pick up to `count` events from now until `days` ahead, including today's
+// all-day events
+pub fn upcoming(
+    events: &[CalendarEvent],
+    now: DateTime<Local>,
+    days: u32,
+    count: usize,
+) -> Vec<&CalendarEvent> {
+    let until = now + Duration::days(days as i64);
+    events
+        .iter()
+        .filter(|event| {
+            let started = if event.all_day {
+                event.date() < now.date_naive()
+            } else {
+                event.start < now
+            };
+            !started && event.start < until
+        })
+        .take(count)
+        .collect()
+}
+
+fn to_local(date_time: CalendarDateTime) -> Option<DateTime<Local>> {
+    match date_time {
+        CalendarDateTime::Utc(utc) => Some(utc.with_timezone(&Local)),
+        CalendarDateTime::Floating(naive) => local_time(naive),
+        // Resolve the TZID through the system time zone database, falling back to local time
+        CalendarDateTime::WithTimezone { date_time, tzid } => {
+            glib::TimeZone::from_identifier(Some(&tzid))
+                .and_then(|zone| {
+                    glib::DateTime::new(
+                        &zone,
+                        date_time.year(),
+                        date_time.month() as i32,
+                        date_time.day() as i32,
+                        date_time.hour() as i32,
+                        date_time.minute() as i32,
+                        date_time.second() as f64,
+                    )
+                    .ok()
+                })
+                .and_then(|zoned| Local.timestamp_opt(zoned.to_unix(), 0).single())
+                .or_else(|| local_time(date_time))
+        }
+    }
+}
+
+fn local_time(naive: NaiveDateTime) -> Option<DateTime<Local>> {
+    Local.from_local_datetime(&naive).earliest()
+}
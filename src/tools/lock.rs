@@ -0,0 +1,33 @@
+// src/tools/lock.rs
+// github.com/cvusmo/hyprclock
+
+// The window, every bar process and the CLI share the state files. Whoever changes one holds
+// this lock from load to save, so updates are not lost and due events fire only once.
+
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+// Exclusive advisory lock on `<state file>.lock`, released when dropped
+pub struct StateLock {
+    _file: File,
+}
+
+impl StateLock {
+    // Function to wait for the lock next to a state file
+    pub fn acquire(path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path.with_extension("lock"))?;
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(StateLock { _file: file })
+    }
+}
@@ -0,0 +1,120 @@
+// src/tools/notify.rs
+// github.com/cvusmo/hyprclock
+
+use gio::prelude::*;
+use gio::{
+    BusType, Cancellable, DBusCallFlags, DBusConnection, DBusSignalFlags, SignalSubscriptionId,
+};
+use glib::{Variant, VariantTy};
+use std::collections::HashMap;
+use std::process::Command;
+
+const NOTIFICATIONS_NAME: &str = "org.freedesktop.Notifications";
+const NOTIFICATIONS_PATH: &str = "/org/freedesktop/Notifications";
+
+fn session_bus() -> Result<DBusConnection, String> {
+    gio::bus_get_sync(BusType::Session, Cancellable::NONE)
+        .map_err(|e| format!("Failed to connect to the session bus: {}", e))
+}
+
+// Function to raise a freedesktop notification; actions are (key, label) pairs
+pub fn send_notification(
+    summary: &str,
+    body: &str,
+    actions: &[(&str, &str)],
+) -> Result<u32, String> {
+    notify(summary, body, actions, 1)
+}
+
+// Function to raise a critical notification that stays until it is answered
+pub fn send_urgent_notification(
+    summary: &str,
+    body: &str,
+    actions: &[(&str, &str)],
+) -> Result<u32, String> {
+    notify(summary, body, actions, 2)
+}
+
+fn notify(summary: &str, body: &str, actions: &[(&str, &str)], urgency: u8) -> Result<u32, String> {
+    let actions: Vec<String> = actions
+        .iter()
+        .flat_map(|(key, label)| [key.to_string(), label.to_string()])
+        .collect();
+    let mut hints: HashMap<String, Variant> = HashMap::new();
+    hints.insert("urgency".to_string(), urgency.to_variant());
+    let parameters = (
+        "Hyprclock",
+        0u32,
+        "alarm-symbolic",
+        summary,
+        body,
+        actions,
+        hints,
+        -1i32,
+    )
+        .to_variant();
+
+    let reply = session_bus()?
+        .call_sync(
+            Some(NOTIFICATIONS_NAME),
+            NOTIFICATIONS_PATH,
+            NOTIFICATIONS_NAME,
+            "Notify",
+            Some(&parameters),
+            Some(VariantTy::new("(u)").expect("Invalid reply type")),
+            DBusCallFlags::NONE,
+            -1,
+            Cancellable::NONE,
+        )
+        .map_err(|e| format!("Failed to send notification: {}", e))?;
+
+    Ok(reply.get::<(u32,)>().map(|(id,)| id).unwrap_or(0))
+}
+
+// Function to listen for notification actions; closing a notification reports the "closed" action
+pub fn connect_notification_actions<F: Fn(u32, &str) + 'static>(
+    callback: F,
+) -> Result<SignalSubscriptionId, String> {
+    // The shared session connection lives for the whole process, so the subscription lasts
+    // until it is disconnected
+    let subscription = session_bus()?.signal_subscribe(
+        Some(NOTIFICATIONS_NAME),
+        Some(NOTIFICATIONS_NAME),
+        None,
+        Some(NOTIFICATIONS_PATH),
+        None,
+        DBusSignalFlags::NONE,
+        move |_, _, _, _, signal, parameters| match signal {
+            "ActionInvoked" => {
+                if let Some((id, action)) = parameters.get::<(u32, String)>() {
+                    callback(id, &action);
+                }
+            }
+            "NotificationClosed" => {
+                if let Some((id, _reason)) = parameters.get::<(u32, u32)>() {
+                    callback(id, "closed");
+                }
+            }
+            _ => {}
+        },
+    );
+    Ok(subscription)
+}
+
+// Function to stop a listener from connect_notification_actions
+pub fn disconnect_notification_actions(subscription: SignalSubscriptionId) -> Result<(), String> {
+    session_bus()?.signal_unsubscribe(subscription);
+    Ok(())
+}
+
+// Function to run a user hook through the shell with extra environment variables
+pub fn run_hook(command: &str, env: &[(&str, &str)]) -> Result<(), String> {
+    let mut hook = Command::new("sh");
+    hook.arg("-c").arg(command);
+    for (key, value) in env {
+        hook.env(key, value);
+    }
+    hook.spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to run '{}': {}", command, e))
+}
@@ -0,0 +1,337 @@
+// src/tools/pomodoro.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::config::get_state_dir;
+use crate::configuration::pomodoro::PomodoroConfig;
+use crate::tools::lock::StateLock;
+use crate::tools::notify::send_notification;
+use crate::tools::timers::{format_remaining, now_unix};
+use chrono::{Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    #[default]
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl Phase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Work => "Focus",
+            Phase::ShortBreak => "Short break",
+            Phase::LongBreak => "Long break",
+        }
+    }
+
+    pub fn css_class(&self) -> &'static str {
+        match self {
+            Phase::Work => "pomodoro-work",
+            Phase::ShortBreak => "pomodoro-short-break",
+            Phase::LongBreak => "pomodoro-long-break",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Phase::Work => "🍅",
+            Phase::ShortBreak | Phase::LongBreak => "☕",
+        }
+    }
+
+    pub fn seconds(&self, config: &PomodoroConfig) -> i64 {
+        let minutes = match self {
+            Phase::Work => config.work_minutes,
+            Phase::ShortBreak => config.short_break_minutes,
+            Phase::LongBreak => config.long_break_minutes,
+        };
+        minutes as i64 * 60
+    }
+}
+
+// A finished focus session, one JSON object per line in the history file
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Session {
+    pub started: i64, // unix seconds
+    pub ended: i64,   // unix seconds
+}
+
+impl Session {
+    pub fn minutes(&self) -> i64 {
+        (self.ended - self.started) / 60
+    }
+}
+
+pub struct PhaseChange {
+    pub finished: Phase,
+    pub next: Option<Phase>, // None once the long break ends the set
+}
+
+// The current cycle, persisted so it survives a restart
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct Pomodoro {
+    pub phase: Phase,
+    pub started_at: Option<i64>, // unix seconds
+    pub length: i64,             // seconds, fixed when the phase starts
+    pub completed: u32,          // focus sessions finished in this set
+}
+
+impl Pomodoro {
+    /// Load the cycle from the state file
+    pub fn load() -> Self {
+        fs::read_to_string(get_pomodoro_path())
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the cycle to the state file
+    pub fn save(&self) -> io::Result<()> {
+        let path = get_pomodoro_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    pub fn start(&mut self, now: i64, config: &PomodoroConfig) {
+        if !self.is_running() {
+            self.begin(self.phase, now, config);
+        }
+    }
+
+    // Stop and reset to the start of a new set
+    pub fn stop(&mut self) {
+        *self = Pomodoro::default();
+    }
+
+    // Start when stopped, stop when running, for a single click binding
+    pub fn toggle(&mut self, now: i64, config: &PomodoroConfig) {
+        if self.is_running() {
+            self.stop();
+        } else {
+            self.start(now, config);
+        }
+    }
+
+    // End the current phase early; a skipped focus session is not logged
+    pub fn skip(&mut self, now: i64, config: &PomodoroConfig) -> Option<Phase> {
+        let next = match self.phase {
+            // ...and does not count towards the long break either
+            Phase::Work => Some(Phase::ShortBreak),
+            _ => self.next_phase(config),
+        };
+        match next {
+            Some(phase) => self.begin(phase, now, config),
+            None => self.stop(),
+        }
+        next
+    }
+
+    pub fn ends_at(&self) -> Option<i64> {
+        self.started_at.map(|started| started + self.length)
+    }
+
+    pub fn remaining(&self, now: i64) -> i64 {
+        self.ends_at().map_or(0, |ends| (ends - now).max(0))
+    }
+
+    // Fraction of the current phase that has passed, from 0.0 to 1.0
+    pub fn progress(&self, now: i64) -> f64 {
+        match self.started_at {
+            Some(started) if self.length > 0 => {
+                ((now - started) as f64 / self.length as f64).clamp(0.0, 1.0)
+            }
+            _ => 0.0,
+        }
+    }
+
+    // Function to move through every phase that ended by `now`, logging finished focus sessions
+    pub fn advance(
+        &mut self,
+        now: i64,
+        config: &PomodoroConfig,
+    ) -> (Vec<PhaseChange>, Vec<Session>) {
+        let mut changes = Vec::new();
+        let mut sessions = Vec::new();
+
+        while let (Some(started), Some(ends)) = (self.started_at, self.ends_at()) {
+            if ends > now {
+                break;
+            }
+            let finished = self.phase;
+            if finished == Phase::Work {
+                sessions.push(Session {
+                    started,
+                    ended: ends,
+                });
+            }
+            let next = self.next_phase(config);
+            match next {
+                // The next phase starts when this one ended, so catching up stays accurate
+                Some(phase) => self.begin(phase, ends, config),
+                None => self.stop(),
+            }
+            changes.push(PhaseChange { finished, next });
+        }
+
+        (changes, sessions)
+    }
+
+    pub fn status(&self, now: i64) -> String {
+        format!(
+            "{} {}",
+            self.phase.icon(),
+            format_remaining(self.remaining(now))
+        )
+    }
+
+    fn begin(&mut self, phase: Phase, now: i64, config: &PomodoroConfig) {
+        self.phase = phase;
+        self.started_at = Some(now);
+        self.length = phase.seconds(config);
+    }
+
+    fn next_phase(&mut self, config: &PomodoroConfig) -> Option<Phase> {
+        match self.phase {
+            Phase::Work => {
+                self.completed += 1;
+                if self.completed >= config.cycles {
+                    Some(Phase::LongBreak)
+                } else {
+                    Some(Phase::ShortBreak)
+                }
+            }
+            Phase::ShortBreak => Some(Phase::Work),
+            Phase::LongBreak => None,
+        }
+    }
+}
+
+/// Get the path of the pomodoro state file
+pub fn get_pomodoro_path() -> PathBuf {
+    get_state_dir().join("pomodoro.json")
+}
+
+/// Get the path of the focus session history
+pub fn get_history_path() -> PathBuf {
+    get_state_dir().join("pomodoro-history.jsonl")
+}
+
+// Function to append finished focus sessions to the history file
+pub fn append_history(sessions: &[Session]) -> io::Result<()> {
+    let path = get_history_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for session in sessions {
+        let line = serde_json::to_string(session)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        writeln!(file, "{}", line)?;
+    }
+    Ok(())
+}
+
+// Function to total focus minutes per local day, oldest first
+pub fn daily_totals() -> io::Result<Vec<(NaiveDate, i64)>> {
+    let contents = match fs::read_to_string(get_history_path()) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut totals: BTreeMap<NaiveDate, i64> = BTreeMap::new();
+    for session in contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Session>(line).ok())
+    {
+        if let Some(started) = Local.timestamp_opt(session.started, 0).single() {
+            *totals.entry(started.date_naive()).or_default() += session.minutes();
+        }
+    }
+    Ok(totals.into_iter().collect())
+}
+
+// Function to announce a phase change through a notification
+pub fn announce(change: &PhaseChange, config: &PomodoroConfig) -> Result<(), String> {
+    let (summary, body) = match change.next {
+        Some(Phase::Work) => (
+            "Back to focus".to_string(),
+            format!("{} minutes of focus", config.work_minutes),
+        ),
+        Some(phase) => (
+            format!("{} finished", change.finished.name()),
+            format!("{}: {} minutes", phase.name(), phase.seconds(config) / 60),
+        ),
+        None => (
+            "Pomodoro set complete".to_string(),
+            format!("{} focus sessions done", config.cycles),
+        ),
+    };
+    send_notification(&summary, &body, &[]).map(|_| ())
+}
+
+// Function to load the cycle, apply due phase changes and persist the result
+pub fn collect_phase_changes(config: &PomodoroConfig) -> (Pomodoro, Vec<String>) {
+    update_pomodoro(config, |_| {})
+}
+
+// Function to apply due phase changes and then `change` under the state lock. Only the process
+// holding the lock sees a phase end, so with several bars and the window running each session
+// is logged and announced once.
+pub fn update_pomodoro(
+    config: &PomodoroConfig,
+    change: impl FnOnce(&mut Pomodoro),
+) -> (Pomodoro, Vec<String>) {
+    let lock = match StateLock::acquire(&get_pomodoro_path()) {
+        Ok(lock) => lock,
+        Err(err) => {
+            return (
+                Pomodoro::load(),
+                vec![format!("Failed to lock pomodoro: {}", err)],
+            )
+        }
+    };
+    let mut pomodoro = Pomodoro::load();
+    let loaded = pomodoro.clone();
+    let (changes, sessions) = pomodoro.advance(now_unix(), config);
+    change(&mut pomodoro);
+
+    let mut errors = Vec::new();
+    if !sessions.is_empty() {
+        if let Err(err) = append_history(&sessions) {
+            errors.push(format!("Failed to save pomodoro history: {}", err));
+        }
+    }
+    // Saving only on a change keeps the window's file monitor quiet
+    if pomodoro != loaded {
+        if let Err(err) = pomodoro.save() {
+            errors.push(format!("Failed to save pomodoro: {}", err));
+        }
+    }
+    drop(lock);
+
+    if config.notify {
+        // Catching up after a long gap only announces where the cycle is now
+        if let Some(change) = changes.last() {
+            if let Err(err) = announce(change, config) {
+                errors.push(err);
+            }
+        }
+    }
+    (pomodoro, errors)
+}
@@ -0,0 +1,131 @@
+// src/tools/stopwatch.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::config::get_state_dir;
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+// Stopwatch state, persisted so the bar output can read it
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Stopwatch {
+    pub started_at: Option<f64>, // unix seconds of the last start, None while stopped
+    pub accumulated: f64,        // seconds counted before the last start
+    pub laps: Vec<f64>,          // total elapsed seconds at each lap
+}
+
+impl Stopwatch {
+    /// Load the stopwatch from the state file
+    pub fn load() -> Self {
+        fs::read_to_string(get_stopwatch_path())
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the stopwatch to the state file
+    pub fn save(&self) -> io::Result<()> {
+        let path = get_stopwatch_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.started_at.is_some()
+    }
+
+    // Nothing to show until the stopwatch has been started once
+    pub fn is_idle(&self) -> bool {
+        !self.is_running() && self.accumulated == 0.0
+    }
+
+    pub fn elapsed(&self, now: f64) -> f64 {
+        self.accumulated + self.started_at.map_or(0.0, |start| (now - start).max(0.0))
+    }
+
+    pub fn start(&mut self, now: f64) {
+        if self.started_at.is_none() {
+            self.started_at = Some(now);
+        }
+    }
+
+    pub fn stop(&mut self, now: f64) {
+        self.accumulated = self.elapsed(now);
+        self.started_at = None;
+    }
+
+    pub fn lap(&mut self, now: f64) {
+        if self.is_running() {
+            self.laps.push(self.elapsed(now));
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Stopwatch::default();
+    }
+
+    // Lap number, lap time and total time for each lap
+    pub fn lap_times(&self) -> Vec<(usize, f64, f64)> {
+        let mut previous = 0.0;
+        self.laps
+            .iter()
+            .enumerate()
+            .map(|(i, total)| {
+                let lap = (i + 1, total - previous, *total);
+                previous = *total;
+                lap
+            })
+            .collect()
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("lap,lap_time,total_time,lap_seconds,total_seconds\n");
+        for (number, lap, total) in self.lap_times() {
+            csv.push_str(&format!(
+                "{},{},{},{:.2},{:.2}\n",
+                number,
+                format_centiseconds(lap),
+                format_centiseconds(total),
+                truncate_centiseconds(lap),
+                truncate_centiseconds(total)
+            ));
+        }
+        csv
+    }
+
+    pub fn export_csv(&self, path: &PathBuf) -> io::Result<()> {
+        fs::write(path, self.to_csv())
+    }
+}
+
+/// Get the path of the stopwatch state file
+pub fn get_stopwatch_path() -> PathBuf {
+    get_state_dir().join("stopwatch.json")
+}
+
+pub fn now_precise() -> f64 {
+    chrono::Utc::now().timestamp_micros() as f64 / 1_000_000.0
+}
+
+// Drop anything below a centisecond so the CSV columns agree with the display
+fn truncate_centiseconds(seconds: f64) -> f64 {
+    (seconds.max(0.0) * 100.0).floor() / 100.0
+}
+
+// Function to format seconds as MM:SS.cc or H:MM:SS.cc
+pub fn format_centiseconds(seconds: f64) -> String {
+    let centiseconds = (seconds.max(0.0) * 100.0).floor() as u64;
+    let (hours, minutes) = (centiseconds / 360_000, (centiseconds / 6000) % 60);
+    let (seconds, centiseconds) = ((centiseconds / 100) % 60, centiseconds % 100);
+    if hours > 0 {
+        format!(
+            "{}:{:02}:{:02}.{:02}",
+            hours, minutes, seconds, centiseconds
+        )
+    } else {
+        format!("{:02}:{:02}.{:02}", minutes, seconds, centiseconds)
+    }
+}
@@ -0,0 +1,199 @@
+// src/tools/timers.rs
+// github.com/cvusmo/hyprclock
+
+use crate::configuration::config::get_state_dir;
+use crate::configuration::timers::TimersConfig;
+use crate::tools::lock::StateLock;
+use crate::tools::notify::{run_hook, send_notification};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Timer {
+    pub name: String,
+    pub duration: i64, // seconds
+    pub ends_at: i64,  // unix seconds
+}
+
+impl Timer {
+    pub fn remaining(&self, now: i64) -> i64 {
+        (self.ends_at - now).max(0)
+    }
+
+    pub fn is_finished(&self, now: i64) -> bool {
+        self.ends_at <= now
+    }
+}
+
+// Running timers, persisted so they survive a restart
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TimerStore {
+    pub timers: Vec<Timer>,
+}
+
+impl TimerStore {
+    /// Load running timers from the state file
+    pub fn load() -> Self {
+        fs::read_to_string(get_timers_path())
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save running timers to the state file
+    pub fn save(&self) -> io::Result<()> {
+        let path = get_timers_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+
+    // Function to change the stored timers under the state lock
+    pub fn update<R>(change: impl FnOnce(&mut TimerStore) -> R) -> io::Result<R> {
+        let _lock = StateLock::acquire(&get_timers_path())?;
+        let mut store = TimerStore::load();
+        let result = change(&mut store);
+        store.save()?;
+        Ok(result)
+    }
+
+    // Start a timer, replacing any running timer with the same name
+    pub fn add(&mut self, name: &str, duration: i64, now: i64) {
+        self.cancel(name);
+        self.timers.push(Timer {
+            name: name.to_string(),
+            duration,
+            ends_at: now + duration,
+        });
+        self.timers.sort_by_key(|timer| timer.ends_at);
+    }
+
+    pub fn cancel(&mut self, name: &str) -> bool {
+        let count = self.timers.len();
+        self.timers.retain(|timer| timer.name != name);
+        self.timers.len() != count
+    }
+
+    // Remove and return every timer that has run out
+    pub fn take_finished(&mut self, now: i64) -> Vec<Timer> {
+        let (finished, running): (Vec<Timer>, Vec<Timer>) = self
+            .timers
+            .drain(..)
+            .partition(|timer| timer.is_finished(now));
+        self.timers = running;
+        finished
+    }
+
+    pub fn soonest(&self) -> Option<&Timer> {
+        self.timers.iter().min_by_key(|timer| timer.ends_at)
+    }
+}
+
+/// Get the path of the running timers state file
+pub fn get_timers_path() -> PathBuf {
+    get_state_dir().join("timers.json")
+}
+
+pub fn now_unix() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+// Function to parse durations such as "90s", "15m", "1h30m" or a bare number of minutes
+pub fn parse_duration(text: &str) -> Result<i64, String> {
+    let text = text.trim();
+    if let Ok(minutes) = text.parse::<i64>() {
+        return if minutes > 0 {
+            Ok(minutes * 60)
+        } else {
+            Err(format!("Duration must be positive: {}", text))
+        };
+    }
+
+    let mut total = 0;
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let value: i64 = number
+            .parse()
+            .map_err(|_| format!("Invalid duration: {}", text))?;
+        total += match c {
+            'h' => value * 3600,
+            'm' => value * 60,
+            's' => value,
+            _ => return Err(format!("Invalid duration unit '{}' in {}", c, text)),
+        };
+        number.clear();
+    }
+
+    if !number.is_empty() || total <= 0 {
+        return Err(format!("Invalid duration: {}", text));
+    }
+    Ok(total)
+}
+
+// Function to format remaining seconds as MM:SS or H:MM:SS
+pub fn format_remaining(seconds: i64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, (seconds % 3600) / 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+pub fn format_timer(timer: &Timer, now: i64) -> String {
+    format!("{} {}", timer.name, format_remaining(timer.remaining(now)))
+}
+
+// Function to announce finished timers through a notification and the command hook
+pub fn finish_timers(config: &TimersConfig, finished: &[Timer]) -> Vec<String> {
+    let mut errors = Vec::new();
+    for timer in finished {
+        if config.notify {
+            let body = format!("{} is up", format_remaining(timer.duration));
+            if let Err(err) = send_notification(&timer.name, &body, &[]) {
+                errors.push(err);
+            }
+        }
+        if let Some(command) = &config.command {
+            if let Err(err) = run_hook(command, &[("HYPRCLOCK_TIMER", &timer.name)]) {
+                errors.push(err);
+            }
+        }
+    }
+    errors
+}
+
+// Function to load the store, fire finished timers and persist what is left. Timers are taken
+// under the state lock, so with several bars and the window running each fires once.
+pub fn collect_finished(config: &TimersConfig) -> (TimerStore, Vec<String>) {
+    let lock = match StateLock::acquire(&get_timers_path()) {
+        Ok(lock) => lock,
+        // Without the lock only show the timers; whoever holds it fires them
+        Err(err) => {
+            return (
+                TimerStore::load(),
+                vec![format!("Failed to lock timers: {}", err)],
+            )
+        }
+    };
+    let mut store = TimerStore::load();
+    let finished = store.take_finished(now_unix());
+    if finished.is_empty() {
+        return (store, Vec::new());
+    }
+
+    let mut errors = Vec::new();
+    if let Err(err) = store.save() {
+        errors.push(format!("Failed to save timers: {}", err));
+    }
+    drop(lock);
+    errors.extend(finish_timers(config, &finished));
+    (store, errors)
+}
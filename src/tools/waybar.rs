@@ -0,0 +1,74 @@
+// src/tools/waybar.rs
+// github.com/cvusmo/hyprclock
+
+// Bar classes and percentage worked out from the clock's state and the [waybar] rules
+
+use crate::configuration::config::Config;
+use crate::time::sun::SunTimes;
+use crate::tools::events::CalendarEvent;
+use chrono::{DateTime, Duration, Local, NaiveTime, Utc};
+
+// Function to evaluate the configured class rules at an instant
+pub fn classes(
+    config: &Config,
+    now: DateTime<Local>,
+    events: &[CalendarEvent],
+    alarm_ringing: bool,
+) -> Vec<&'static str> {
+    let rules = &config.waybar;
+    let mut classes = Vec::new();
+
+    if rules.uses("night") && is_night(config, now) {
+        classes.push("night");
+    }
+    if rules.uses("event-soon") {
+        let soon = now + Duration::minutes(rules.event_soon_minutes as i64);
+        if events
+            .iter()
+            .any(|event| !event.all_day && event.start > now && event.start <= soon)
+        {
+            classes.push("event-soon");
+        }
+    }
+    if rules.uses("in-meeting") && events.iter().any(|event| event.is_happening(now)) {
+        classes.push("in-meeting");
+    }
+    if rules.uses("alarm") && alarm_ringing {
+        classes.push("alarm");
+    }
+    classes
+}
+
+// The sun decides when a location is configured, the night_start/night_end window otherwise
+fn is_night(config: &Config, now: DateTime<Local>) -> bool {
+    match config.general.coordinates() {
+        Some((latitude, longitude)) => {
+            !SunTimes::new(now.date_naive(), latitude, longitude).is_day(now.with_timezone(&Utc))
+        }
+        None => {
+            let (start, end) = config.waybar.night();
+            let time = now.time();
+            if start <= end {
+                start <= time && time < end
+            } else {
+                time >= start || time < end
+            }
+        }
+    }
+}
+
+// Function to work out how much of the day or work day has gone, 0 to 100
+pub fn percentage(config: &Config, now: DateTime<Local>) -> Option<u8> {
+    let time = now.time();
+    let (start, end) = match config.waybar.percentage.as_str() {
+        "day" => (NaiveTime::MIN, None),
+        "workday" => {
+            let (start, end) = config.waybar.work_day();
+            (start, Some(end))
+        }
+        _ => return None,
+    };
+    let elapsed = (time - start).num_seconds() as f64;
+    let length = end.map_or(86_400.0, |end| (end - start).num_seconds() as f64);
+    Some(((elapsed / length).clamp(0.0, 1.0) * 100.0).round() as u8)
+}